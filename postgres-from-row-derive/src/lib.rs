@@ -1,4 +1,7 @@
-use darling::{ast::Data, Error, FromDeriveInput, FromField, ToTokens};
+use darling::{
+    ast::{Data, Fields},
+    Error, FromDeriveInput, FromField, FromMeta, FromVariant, ToTokens,
+};
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
@@ -16,231 +19,4242 @@ pub fn derive_from_row(input: TokenStream) -> TokenStream {
 
 /// Fallible entry point for generating a `FromRow` implementation
 fn try_derive_from_row(input: &DeriveInput) -> std::result::Result<TokenStream, Error> {
+    check_shape(input, "FromRow")?;
     let from_row_derive = DeriveFromRow::from_derive_input(input)?;
     Ok(from_row_derive.generate()?)
 }
 
-/// Main struct for deriving `FromRow` for a struct.
+/// Calls the fallible entry point and writes any errors to the tokenstream.
+#[proc_macro_derive(ToRowParams, attributes(from_row))]
+pub fn derive_to_row_params(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    match try_derive_to_row_params(&derive_input) {
+        Ok(result) => result,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
+/// Fallible entry point for generating a `ToRowParams` implementation
+fn try_derive_to_row_params(input: &DeriveInput) -> std::result::Result<TokenStream, Error> {
+    check_shape(input, "ToRowParams")?;
+    let from_row_derive = DeriveFromRow::from_derive_input(input)?;
+    Ok(from_row_derive.generate_to_row_params()?)
+}
+
+/// Calls the fallible entry point and writes any errors to the tokenstream.
+#[proc_macro_derive(FromGroupedRows, attributes(from_row))]
+pub fn derive_from_grouped_rows(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    match try_derive_from_grouped_rows(&derive_input) {
+        Ok(result) => result,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
+/// Fallible entry point for generating a `FromGroupedRows` implementation
+fn try_derive_from_grouped_rows(input: &DeriveInput) -> std::result::Result<TokenStream, Error> {
+    check_shape(input, "FromGroupedRows")?;
+    let from_row_derive = DeriveFromRow::from_derive_input(input)?;
+    Ok(from_row_derive.generate_grouped()?)
+}
+
+/// Rejects the shapes that none of the derives support with a message that names the supported ones, instead of the
+/// generic error of darling.
+fn check_shape(input: &DeriveInput, derive: &str) -> darling::Result<()> {
+    match &input.data {
+        syn::Data::Union(data) => Err(Error::custom(format!(
+            "`{derive}` can't be derived for unions, only for structs and for enums whose variants have named fields or no fields"
+        ))
+        .with_span(&data.union_token)),
+        syn::Data::Enum(data) => match data
+            .variants
+            .iter()
+            .find(|variant| matches!(variant.fields, syn::Fields::Unnamed(_)))
+        {
+            Some(variant) => Err(Error::custom(format!(
+                "`{derive}` can only be derived for enums whose variants have named fields or no fields, use `{} {{ .. }}` instead of a tuple variant",
+                variant.ident
+            ))
+            .with_span(&variant.ident)),
+            None => Ok(()),
+        },
+        syn::Data::Struct(_) => Ok(()),
+    }
+}
+
+/// Calls the fallible entry point and writes any errors to the tokenstream.
+#[proc_macro_derive(FromRowScalar, attributes(from_row))]
+pub fn derive_from_row_scalar(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    match try_derive_from_row_scalar(&derive_input) {
+        Ok(result) => result,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
+/// Fallible entry point for generating a `FromSql` implementation for a newtype.
+fn try_derive_from_row_scalar(input: &DeriveInput) -> std::result::Result<TokenStream, Error> {
+    check_shape(input, "FromRowScalar")?;
+    let from_row_derive = DeriveFromRow::from_derive_input(input)?;
+    Ok(from_row_derive.generate_scalar()?)
+}
+
+/// Main struct for deriving `FromRow` for a struct or an enum.
 #[derive(Debug, FromDeriveInput)]
 #[darling(
     attributes(from_row),
     forward_attrs(allow, doc, cfg),
-    supports(struct_named)
+    supports(struct_named, struct_tuple, struct_unit, enum_named, enum_unit)
 )]
 struct DeriveFromRow {
     ident: syn::Ident,
     generics: syn::Generics,
-    data: Data<(), FromRowField>,
+    data: Data<FromRowVariant, FromRowField>,
+    /// The column that selects the variant when deriving for an enum, required for enums.
+    tag: Option<String>,
+    /// Rename all fields that don't specify `#[from_row(rename = "..")]` according to this rule.
+    rename_all: Option<RenameRule>,
+    /// Wether fields that don't specify `#[from_row(rename = "..")]` follow the naming convention of a struct that
+    /// flattens this one with `#[from_row(flatten, inherit_rename_all)]`. Is not compatible with `rename_all`.
+    #[darling(default)]
+    inherit_rename_all: bool,
+    /// Prepend this prefix to the column name of all fields that don't specify `#[from_row(rename = "..")]`.
+    prefix: Option<String>,
+    /// Compute the column name of all fields that don't specify `#[from_row(rename = "..")]` by replacing the `{}`
+    /// in this format with the name of the field, after `rename_all` is applied.
+    column_format: Option<ColumnFormat>,
+    /// Wether this struct has a single named field, which is read from the column named by
+    /// `#[from_row(rename = "..")]` on the struct.
+    #[darling(default)]
+    transparent: bool,
+    /// The column the single field of a `transparent` struct is read from.
+    rename: Option<String>,
+    /// Wether to fall back to a unicode aware case insensitive match when a column can't be found by its name.
+    #[darling(default)]
+    case_insensitive: bool,
+    /// Wether to generate `resolve_indices` and `from_row_indexed`, which look up the positions of all columns
+    /// once per statement instead of once per row.
+    #[darling(default)]
+    indexed: bool,
+    /// Wether to generate `try_from_row_verbose_with_prefix`, which reports the errors of all columns
+    /// instead of only the first one.
+    #[darling(default)]
+    verbose: bool,
+    /// Wether to generate `try_from_row_detailed_with_prefix`, which reports the column and field that failed
+    /// together with the error.
+    #[darling(default)]
+    detailed: bool,
+    /// Optionaly call this function with the value of every field, in declaration order, instead of
+    /// constructing the struct directly. The function must return `Self`.
+    build_with: Option<syn::ExprPath>,
+    /// Like `build_with`, but the function returns a `Result<Self, E>`. The error is returned by `try_from_row`
+    /// and causes a panic in `from_row`.
+    try_build_with: Option<syn::ExprPath>,
+    /// Wether every field that reads a column behaves as if it had `#[from_row(default)]`, unless it has
+    /// another fallback.
+    #[darling(default)]
+    lenient: bool,
+    /// Wether `from_row` calls `try_from_row` and panics with its error, instead of panicking at each column that
+    /// can't be read. The same applies to `from_row_indexed` and `update_from_row`.
+    #[darling(default)]
+    panic_via_try: bool,
+    /// Wether to also implement `sqlx::FromRow` for postgres rows, which requires the `sqlx` feature.
+    #[darling(default)]
+    sqlx: bool,
+    /// Wether to generate `validate_columns_with_prefix`, which checks that the columns of a statement contain
+    /// every required column in the order they are read.
+    #[darling(default)]
+    validate_columns: bool,
+    /// Wether to also implement `FromSql` for composite types, which reads the fields of the composite type by name.
+    #[darling(default)]
+    composite: bool,
+    /// Optionaly use this path to refer to `postgres_from_row`, for when the crate is re-exported under another name.
+    #[darling(rename = "crate")]
+    krate: Option<syn::Path>,
+    /// Optionaly use this lifetime in the generated predicates and implementations instead of `'__from_row_lifetime`.
+    lifetime: Option<LifetimeName>,
+    /// Optionaly implement the traits for this type instead, which mirrors the struct or enum the derive is applied
+    /// to. All fields of the remote type need to be public.
+    remote: Option<syn::Path>,
 }
 
 impl DeriveFromRow {
-    /// Validates all fields
+    /// Validates all fields, and checks that no two fields of the struct or of a single variant read the same column.
     fn validate(&self) -> Result<()> {
-        for field in self.fields() {
+        for field in self.all_fields() {
             field.validate()?;
         }
 
-        Ok(())
-    }
-
-    /// Generates any additional where clause predicates needed for the fields in this struct.
-    fn predicates(&self) -> Result<Vec<TokenStream2>> {
-        let mut predicates = Vec::new();
+        self.validate_borrowed()?;
 
-        for field in self.fields() {
-            field.add_predicates(&mut predicates)?;
+        if self.inherit_rename_all {
+            for (present, attribute) in [
+                (
+                    self.rename_all.is_some(),
+                    r#"#[from_row(rename_all = "..")]"#,
+                ),
+                (
+                    self.column_format.is_some(),
+                    r#"#[from_row(column_format = "..")]"#,
+                ),
+                (self.transparent, "#[from_row(transparent)]"),
+            ] {
+                if present {
+                    return Err(Error::custom(format!(
+                        "can't combine `#[from_row(inherit_rename_all)]` with `{attribute}`, the convention of this struct is passed by the struct that flattens it"
+                    ))
+                    .with_span(&self.ident)
+                    .into());
+                }
+            }
         }
 
-        Ok(predicates)
-    }
-
-    /// Provides a slice of this struct's fields.
-    fn fields(&self) -> &[FromRowField] {
-        match &self.data {
-            Data::Struct(fields) => &fields.fields,
-            _ => panic!("invalid shape"),
+        if let Some(LifetimeName(lifetime)) = &self.lifetime {
+            if self
+                .generics
+                .lifetimes()
+                .any(|param| param.lifetime == *lifetime)
+            {
+                return Err(Error::custom(format!(
+                    "`{lifetime}` is already declared on this type, choose another lifetime"
+                ))
+                .with_span(lifetime)
+                .into());
+            }
         }
-    }
 
-    /// Generate the `FromRow` implementation.
-    fn generate(self) -> Result<TokenStream> {
-        self.validate()?;
+        match &self.data {
+            Data::Struct(fields) => {
+                if self.tag.is_some() {
+                    return Err(Error::custom(
+                        r#"`#[from_row(tag = "..")]` can only be used on enums"#,
+                    )
+                    .into());
+                }
 
-        let ident = &self.ident;
+                if self.build_with.is_some() && self.try_build_with.is_some() {
+                    return Err(Error::custom(
+                        r#"`#[from_row(build_with = "..")]` and `#[from_row(try_build_with = "..")]` can't be used together"#,
+                    )
+                    .into());
+                }
 
-        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
-        let original_predicates = where_clause.clone().map(|w| &w.predicates).into_iter();
-        let predicates = self.predicates()?;
+                if self.composite {
+                    self.validate_composite(&fields.fields)?;
+                }
 
-        let from_row_fields = self
-            .fields()
-            .iter()
-            .map(|f| f.generate_from_row())
-            .collect::<syn::Result<Vec<_>>>()?;
+                self.validate_rest(&fields.fields)?;
 
-        let try_from_row_fields = self
-            .fields()
-            .iter()
-            .map(|f| f.generate_try_from_row())
-            .collect::<syn::Result<Vec<_>>>()?;
+                if self.transparent || self.rename.is_some() {
+                    self.validate_transparent(&fields.fields)?;
+                }
 
-        Ok(quote! {
-            impl #impl_generics postgres_from_row::FromRow for #ident #ty_generics where #(#original_predicates),* #(#predicates),* {
+                if self.sqlx {
+                    if self.try_build_with.is_some() {
+                        return Err(Error::custom(
+                            r#"`#[from_row(try_build_with = "..")]` is not supported in combination with `#[from_row(sqlx)]`"#,
+                        )
+                        .into());
+                    }
 
-                fn from_row(row: &postgres_from_row::tokio_postgres::Row) -> Self {
-                    Self {
-                        #(#from_row_fields),*
+                    for field in &fields.fields {
+                        field.validate_sqlx()?;
                     }
                 }
 
-                fn try_from_row(row: &postgres_from_row::tokio_postgres::Row) -> std::result::Result<Self, postgres_from_row::tokio_postgres::Error> {
-                    Ok(Self {
-                        #(#try_from_row_fields),*
-                    })
+                self.validate_columns(&fields.fields)
+            }
+            Data::Enum(variants) => {
+                if self.tag.is_none() {
+                    return Err(Error::custom(
+                        r#"deriving `FromRow` for an enum requires `#[from_row(tag = "..")]`"#,
+                    )
+                    .into());
+                }
+
+                if self.indexed {
+                    return Err(
+                        Error::custom("`#[from_row(indexed)]` is not supported for enums").into(),
+                    );
+                }
+
+                if let Some(field) = self.all_fields().into_iter().find(|field| field.rest) {
+                    return Err(field
+                        .error("`#[from_row(rest)]` is not supported for enums")
+                        .into());
+                }
+
+                if self.verbose {
+                    return Err(
+                        Error::custom("`#[from_row(verbose)]` is not supported for enums").into(),
+                    );
+                }
+
+                if self.detailed {
+                    return Err(Error::custom(
+                        "`#[from_row(detailed)]` is not supported for enums",
+                    )
+                    .into());
+                }
+
+                if self.composite {
+                    return Err(Error::custom(
+                        "`#[from_row(composite)]` is not supported for enums",
+                    )
+                    .into());
+                }
+
+                if self.transparent {
+                    return Err(Error::custom(
+                        "`#[from_row(transparent)]` is not supported for enums",
+                    )
+                    .into());
+                }
+
+                if self.rename.is_some() {
+                    return Err(Error::custom(
+                        r#"`#[from_row(rename = "..")]` is not supported for enums"#,
+                    )
+                    .into());
+                }
+
+                if self.build_with.is_some() {
+                    return Err(Error::custom(
+                        r#"`#[from_row(build_with = "..")]` is not supported for enums"#,
+                    )
+                    .into());
+                }
+
+                if self.try_build_with.is_some() {
+                    return Err(Error::custom(
+                        r#"`#[from_row(try_build_with = "..")]` is not supported for enums"#,
+                    )
+                    .into());
+                }
+
+                if self.sqlx {
+                    return Err(
+                        Error::custom("`#[from_row(sqlx)]` is not supported for enums").into(),
+                    );
+                }
+
+                if self.validate_columns {
+                    return Err(Error::custom(
+                        "`#[from_row(validate_columns)]` is not supported for enums",
+                    )
+                    .into());
+                }
+
+                for (index, variant) in variants.iter().enumerate() {
+                    let tag = variant.tag();
+
+                    if let Some(other) = variants[..index].iter().find(|other| other.tag() == tag) {
+                        return Err(Error::custom(format!(
+                            "variant `{}` has the same tag as variant `{}`: `{tag}`",
+                            variant.ident, other.ident,
+                        ))
+                        .with_span(&variant.ident)
+                        .into());
+                    }
+
+                    self.validate_columns(&variant.fields.fields)?;
                 }
+
+                Ok(())
             }
         }
-        .into())
     }
-}
 
-/// A single field inside of a struct that derives `FromRow`
-#[derive(Debug, FromField)]
-#[darling(attributes(from_row), forward_attrs(allow, doc, cfg))]
-struct FromRowField {
-    /// The identifier of this field.
-    ident: Option<syn::Ident>,
-    /// The type specified in this field.
-    ty: syn::Type,
-    /// Wether to flatten this field. Flattening means calling the `FromRow` implementation
-    /// of `self.ty` instead of extracting it directly from the row.
-    #[darling(default)]
-    flatten: bool,
-    /// Optionaly use this type as the target for `FromRow` or `FromSql`, and then
-    /// call `TryFrom::try_from` to convert it the `self.ty`.
-    try_from: Option<String>,
-    /// Optionaly use this type as the target for `FromRow` or `FromSql`, and then
-    /// call `From::from` to convert it the `self.ty`.
-    from: Option<String>,
-    /// Override the name of the actual sql column instead of using `self.ident`.
-    /// Is not compatible with `flatten` since no column is needed there.
-    rename: Option<String>,
-}
+    /// Checks that the struct has named fields, which are only renamed, skipped or defaulted, as those are the only
+    /// attributes supported when reading the fields of a composite type.
+    fn validate_composite(&self, fields: &[FromRowField]) -> Result<()> {
+        for field in fields {
+            if field.ident.is_none() {
+                return Err(field
+                    .error("`#[from_row(composite)]` requires a struct with named fields")
+                    .into());
+            }
 
-impl FromRowField {
-    /// Checks wether this field has a valid combination of attributes
-    fn validate(&self) -> Result<()> {
-        if self.from.is_some() && self.try_from.is_some() {
+            let supported = [
+                r#"#[from_row(rename = "..")]"#,
+                "#[from_row(default)]",
+                r#"#[from_row(default_fn = "..")]"#,
+                "#[from_row(optional)]",
+                "#[from_row(skip)]",
+            ];
+
+            if let Some(attribute) = field
+                .attributes()
+                .into_iter()
+                .find(|attribute| !supported.contains(attribute))
+            {
+                return Err(field
+                    .error(format!(
+                        "`{attribute}` is not supported in combination with `#[from_row(composite)]`"
+                    ))
+                    .into());
+            }
+
+            if field
+                .rename
+                .as_ref()
+                .is_some_and(|rename| rename.0.len() > 1)
+            {
+                return Err(field
+                    .error(
+                        "`#[from_row(rename = [..])]` is not supported in combination with `#[from_row(composite)]`",
+                    )
+                    .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that at most one of `fields` has `#[from_row(rest)]`, and that the other fields only read columns by a
+    /// name that is known at compile time, which the `rest` field skips.
+    fn validate_rest(&self, fields: &[FromRowField]) -> Result<()> {
+        let mut rest = fields.iter().filter(|field| field.rest);
+
+        if rest.next().is_none() {
+            return Ok(());
+        }
+
+        if let Some(other) = rest.next() {
+            return Err(other
+                .error("only a single field can have `#[from_row(rest)]`")
+                .into());
+        }
+
+        if let Some(field) = fields.iter().enumerate().find_map(|(index, field)| {
+            let unknown = field.inherit_rename_all
+                || field.from_row_fn.is_some()
+                || field.try_from_row_fn.is_some()
+                || (field.flatten && !self.generics.params.is_empty())
+                || (field.reads_column()
+                    && matches!(
+                        field.column(self, index),
+                        Column::Index(_) | Column::Inherited { .. }
+                    ));
+
+            unknown.then_some(field)
+        }) {
+            return Err(field
+                .error("`#[from_row(rest)]` can't tell which columns are read by this field")
+                .into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a `transparent` struct has a single named field that reads the column named by the struct's
+    /// `rename`, and that `rename` isn't used on other structs.
+    fn validate_transparent(&self, fields: &[FromRowField]) -> Result<()> {
+        if !self.transparent {
             return Err(Error::custom(
-                r#"can't combine `#[from_row(from = "..")]` with `#[from_row(try_from = "..")]`"#,
+                r#"`#[from_row(rename = "..")]` on a struct requires `#[from_row(transparent)]`"#,
             )
             .into());
         }
 
-        if self.rename.is_some() && self.flatten {
+        if self.rename.is_none() {
+            return Err(Error::custom(
+                r#"`#[from_row(transparent)]` requires `#[from_row(rename = "..")]` naming the column"#,
+            )
+            .into());
+        }
+
+        let [field] = fields else {
             return Err(Error::custom(
-                r#"can't combine `#[from_row(flatten)]` with `#[from_row(rename = "..")]`"#,
+                "`#[from_row(transparent)]` can only be used on structs with a single field",
             )
+            .with_span(&self.ident)
             .into());
+        };
+
+        if field.ident.is_none() {
+            return Err(field
+                .error("`#[from_row(transparent)]` requires a struct with a named field")
+                .into());
+        }
+
+        for (present, attribute) in [
+            (field.flatten, "#[from_row(flatten)]"),
+            (field.rename.is_some(), r#"#[from_row(rename = "..")]"#),
+            (
+                field.rename_const.is_some(),
+                r#"#[from_row(rename_const = "..")]"#,
+            ),
+            (field.column_index.is_some(), "#[from_row(column = ..)]"),
+            (field.skip, "#[from_row(skip)]"),
+            (
+                field.from_row_fn.is_some(),
+                r#"#[from_row(from_row_fn = "..")]"#,
+            ),
+            (
+                field.try_from_row_fn.is_some(),
+                r#"#[from_row(try_from_row_fn = "..")]"#,
+            ),
+        ] {
+            if present {
+                return Err(field
+                    .error(format!(
+                        "`{attribute}` can't be used on the field of a `#[from_row(transparent)]` struct"
+                    ))
+                    .into());
+            }
         }
 
         Ok(())
     }
 
-    /// Returns a tokenstream of the type that should be returned from either
-    /// `FromRow` (when using `flatten`) or `FromSql`.
-    fn target_ty(&self) -> Result<TokenStream2> {
-        if let Some(from) = &self.from {
-            Ok(from.parse()?)
-        } else if let Some(try_from) = &self.try_from {
-            Ok(try_from.parse()?)
-        } else {
-            Ok(self.ty.to_token_stream())
+    /// Checks that all fields borrow from the row with the same lifetime, and that the attributes which require
+    /// an owned `FromRow` implementation aren't used when they do.
+    fn validate_borrowed(&self) -> Result<()> {
+        let Some(lifetime) = self.borrowed() else {
+            return Ok(());
+        };
+
+        for field in self.all_fields() {
+            if let Some(other) = self
+                .own_lifetimes(&field.ty)
+                .into_iter()
+                .find(|other| *other != lifetime)
+            {
+                return Err(field
+                    .error(format!(
+                        "fields can only borrow from the row with a single lifetime, found `{lifetime}` and `{other}`"
+                    ))
+                    .into());
+            }
         }
-    }
 
-    /// Returns the name that maps to the actuall sql column
-    /// By default this is the same as the rust field name but can be overwritten by `#[from_row(rename = "..")]`.
-    fn column_name(&self) -> String {
-        self.rename
-            .as_ref()
-            .map(Clone::clone)
-            .unwrap_or_else(|| self.ident.as_ref().unwrap().to_string())
+        for (present, attribute) in [
+            (self.indexed, "#[from_row(indexed)]"),
+            (self.verbose, "#[from_row(verbose)]"),
+            (self.detailed, "#[from_row(detailed)]"),
+            (self.composite, "#[from_row(composite)]"),
+            (self.sqlx, "#[from_row(sqlx)]"),
+            (self.validate_columns, "#[from_row(validate_columns)]"),
+        ] {
+            if present {
+                return Err(Error::custom(format!(
+                    "`{attribute}` can't be used on types that borrow from the row"
+                ))
+                .with_span(&lifetime)
+                .into());
+            }
+        }
+
+        Ok(())
     }
 
-    /// Pushes the needed where clause predicates for this field.
-    ///
-    /// By default this is `T: for<'__from_row_lifetime> postgres::types::FromSql<'__from_row_lifetime>`,
-    /// when using `flatten` it's: `T: postgres_from_row::FromRow`
-    /// and when using either `from` or `try_from` attributes it additionally pushes this bound:
-    /// `T: std::convert::From<R>`, where `T` is the type specified in the struct and `R` is the
-    /// type specified in the `[try]_from` attribute.
+    /// Returns the lifetime of this type that its fields borrow from the row with, if any.
     ///
-    /// Note: '__from_row_lifetime is used here to avoid conflicts with common user-specified lifetimes like 'a
-    fn add_predicates(&self, predicates: &mut Vec<TokenStream2>) -> Result<()> {
-        let target_ty = &self.target_ty()?;
-        let ty = &self.ty;
+    /// A field borrows from the row when its type mentions one of the type's own lifetimes, such as `&'a str`.
+    /// These types implement `FromBorrowedRow<'a>` instead of `FromRow`.
+    fn borrowed(&self) -> Option<syn::Lifetime> {
+        self.all_fields()
+            .into_iter()
+            .filter(|field| !field.skip)
+            .find_map(|field| self.own_lifetimes(&field.ty).into_iter().next())
+    }
 
-        predicates.push(if self.flatten {
-            quote! (#target_ty: postgres_from_row::FromRow)
+    /// Returns the lifetime parameters of this type that are mentioned by `ty`.
+    fn own_lifetimes(&self, ty: &syn::Type) -> Vec<syn::Lifetime> {
+        let mut mentioned = Vec::new();
+        mentioned_lifetimes(ty.to_token_stream(), &mut mentioned);
+
+        self.generics
+            .lifetimes()
+            .map(|param| &param.lifetime)
+            .filter(|lifetime| mentioned.contains(&lifetime.ident.to_string()))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the path of the trait this type implements, `FromRow` or `FromBorrowedRow<'a>` when it borrows from the row.
+    fn trait_path(&self) -> TokenStream2 {
+        match self.borrowed() {
+            Some(lifetime) => quote!(postgres_from_row::FromBorrowedRow<#lifetime>),
+            None => quote!(postgres_from_row::FromRow),
+        }
+    }
+
+    /// Wether any field depends on the naming convention that is passed to `from_row_with_rename_all`,
+    /// in which case the implementation reads the fields in that method instead of `from_row_with_prefix`.
+    fn inherits_rename_all(&self) -> bool {
+        self.all_fields()
+            .into_iter()
+            .any(|field| field.inherits_rename_all(self))
+    }
+
+    /// Binds `rename_all` to `None` in the generated methods that aren't passed a naming convention.
+    fn rename_all_binding(&self) -> TokenStream2 {
+        if self.inherits_rename_all() {
+            quote!(let rename_all: std::option::Option<postgres_from_row::RenameRule> = None;)
         } else {
-            quote! (#target_ty: for<'__from_row_lifetime> postgres_from_row::tokio_postgres::types::FromSql<'__from_row_lifetime>)
-        });
+            quote!()
+        }
+    }
 
-        if self.from.is_some() {
-            predicates.push(quote!(#ty: std::convert::From<#target_ty>))
-        } else if self.try_from.is_some() {
-            let try_from = quote!(std::convert::TryFrom<#target_ty>);
+    /// Returns the expression for the naming convention that is passed to fields with `inherit_rename_all`, which
+    /// is the convention of this struct or the one that was passed to it.
+    fn rename_all_expr(&self) -> TokenStream2 {
+        match self.rename_all {
+            Some(rule) => {
+                let variant = quote::format_ident!("{}", format!("{rule:?}"));
+                quote!(std::option::Option::Some(postgres_from_row::RenameRule::#variant))
+            }
+            None => quote!(rename_all),
+        }
+    }
 
-            predicates.push(quote!(#ty: #try_from));
-            predicates.push(quote!(postgres_from_row::tokio_postgres::Error: std::convert::From<<#ty as #try_from>::Error>));
-            predicates.push(quote!(<#ty as #try_from>::Error: std::fmt::Debug));
+    /// Checks that no two of `fields` read the same column, and that no two flattened fields declare the same prefix.
+    fn validate_columns(&self, fields: &[FromRowField]) -> Result<()> {
+        let mut columns: Vec<(Column, usize)> = Vec::new();
+
+        for (index, field) in fields.iter().enumerate() {
+            if !field.reads_column() {
+                continue;
+            }
+
+            let column = field.column(self, index);
+
+            let same = |other: &Column| match (other.name(), column.name()) {
+                (Some(other), Some(name)) => other == name,
+                _ => *other == column,
+            };
+
+            if let Some((_, other)) = columns.iter().find(|(other, _)| same(other)) {
+                return Err(field
+                    .error(format!(
+                        "field `{}` reads the same column as field `{}`: {column}",
+                        field.display_name(index),
+                        fields[*other].display_name(*other),
+                    ))
+                    .into());
+            }
+
+            columns.push((column, index));
+        }
+
+        let mut prefixes: Vec<(&str, usize)> = Vec::new();
+
+        for (index, field) in fields.iter().enumerate() {
+            let (true, Some(prefix)) = (field.flatten, &field.prefix) else {
+                continue;
+            };
+
+            if let Some((_, other)) = prefixes.iter().find(|(other, _)| other == prefix) {
+                return Err(field
+                    .error(format!(
+                        "field `{}` is flattened with the same prefix as field `{}`: `{prefix}`",
+                        field.display_name(index),
+                        fields[*other].display_name(*other),
+                    ))
+                    .into());
+            }
+
+            prefixes.push((prefix, index));
         }
 
         Ok(())
     }
 
-    /// Generate the line needed to retrievee this field from a row when calling `from_row`.
-    fn generate_from_row(&self) -> Result<TokenStream2> {
-        let ident = self.ident.as_ref().unwrap();
-        let column_name = self.column_name();
-        let field_ty = &self.ty;
-        let target_ty = self.target_ty()?;
+    /// Generates any additional where clause predicates needed for the fields in this struct.
+    fn predicates(&self) -> Result<Vec<TokenStream2>> {
+        let mut predicates = Vec::new();
 
-        let mut base = if self.flatten {
-            quote!(<#target_ty as postgres_from_row::FromRow>::from_row(row))
-        } else {
-            quote!(postgres_from_row::tokio_postgres::Row::get::<&str, #target_ty>(row, #column_name))
-        };
+        for field in self.all_fields() {
+            field.add_predicates(self, &mut predicates)?;
+        }
 
-        if self.from.is_some() {
-            base = quote!(<#field_ty as std::convert::From<#target_ty>>::from(#base));
-        } else if self.try_from.is_some() {
-            base = quote!(<#field_ty as std::convert::TryFrom<#target_ty>>::try_from(#base).expect("could not convert column"));
-        };
+        Ok(predicates)
+    }
 
-        Ok(quote!(#ident: #base))
+    /// Provides a slice of this struct's fields, in declaration order.
+    /// This slice is empty for structs without fields, their implementation doesn't read anything from the row.
+    ///
+    /// Panics when deriving for an enum, use `all_fields` when the fields of all variants are needed.
+    fn fields(&self) -> &[FromRowField] {
+        match &self.data {
+            Data::Struct(fields) => &fields.fields,
+            _ => panic!("invalid shape"),
+        }
     }
 
-    /// Generate the line needed to retrieve this field from a row when calling `try_from_row`.
-    fn generate_try_from_row(&self) -> Result<TokenStream2> {
-        let ident = self.ident.as_ref().unwrap();
-        let column_name = self.column_name();
-        let field_ty = &self.ty;
-        let target_ty = self.target_ty()?;
+    /// Returns the candidate names of the fields that are read with `#[from_row(rename = [..])]`, of which `COLUMNS`
+    /// only lists the first, for the `rest` field to skip the one that is read.
+    fn rest_candidates(&self) -> TokenStream2 {
+        let candidates = self
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.reads_column())
+            .filter_map(|(index, field)| match field.column(self, index) {
+                Column::Candidates(names) => Some(quote!(&[#(#names),*])),
+                _ => None,
+            });
 
-        let mut base = if self.flatten {
-            quote!(<#target_ty as postgres_from_row::FromRow>::try_from_row(row)?)
-        } else {
-            quote!(postgres_from_row::tokio_postgres::Row::try_get::<&str, #target_ty>(row, #column_name)?)
-        };
+        quote!(&[#(#candidates),*])
+    }
 
-        if self.from.is_some() {
-            base = quote!(<#field_ty as std::convert::From<#target_ty>>::from(#base));
-        } else if self.try_from.is_some() {
-            base = quote!(<#field_ty as std::convert::TryFrom<#target_ty>>::try_from(#base)?);
+    /// Provides the fields of this struct, or the fields of all variants when deriving for an enum.
+    fn all_fields(&self) -> Vec<&FromRowField> {
+        match &self.data {
+            Data::Struct(fields) => fields.fields.iter().collect(),
+            Data::Enum(variants) => variants
+                .iter()
+                .flat_map(|variant| &variant.fields.fields)
+                .collect(),
+        }
+    }
+
+    /// Generate the `FromRow` implementation.
+    fn generate(self) -> Result<TokenStream> {
+        self.validate()?;
+
+        if let Some(field) = self
+            .all_fields()
+            .into_iter()
+            .find(|field| field.children || field.group_key)
+        {
+            return Err(field
+                .error("`#[from_row(children)]` and `#[from_row(group_key)]` can only be used with `#[derive(FromGroupedRows)]`")
+                .into());
+        }
+
+        let ident = &self.ident;
+
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+        let self_ty = self.self_ty();
+        let predicates = merge_predicates(where_clause, self.predicates()?);
+
+        let (from_row_body, try_from_row_body) = match &self.data {
+            Data::Struct(fields) => {
+                let (from_row_values, try_from_row_values) =
+                    self.generate_fields(&fields.fields, &[], &ident.to_string())?;
+                let from_row = self.construct(quote!(Self), &fields.fields, &from_row_values)?;
+                let try_from_row =
+                    self.try_construct(quote!(Self), &fields.fields, &try_from_row_values)?;
+
+                (from_row, try_from_row)
+            }
+            Data::Enum(variants) => self.generate_enum(variants)?,
+        };
+
+        let (indexed, indexed_marker) = if self.indexed {
+            (
+                self.generate_indexed()?,
+                quote!(impl #impl_generics postgres_from_row::Indexed for #self_ty where #(#predicates,)* {}),
+            )
+        } else {
+            (quote!(), quote!())
+        };
+
+        let verbose = if self.verbose {
+            self.generate_verbose()?
+        } else {
+            quote!()
+        };
+
+        let detailed = if self.detailed {
+            self.generate_detailed()?
+        } else {
+            quote!()
+        };
+
+        let columns = self.generate_columns()?;
+
+        let validate_columns = if self.validate_columns {
+            self.generate_validate_columns()?
+        } else {
+            quote!()
+        };
+
+        let sqlx = if self.sqlx {
+            self.generate_sqlx()?
+        } else {
+            quote!()
+        };
+
+        let composite = if self.composite {
+            self.generate_composite()?
+        } else {
+            quote!()
+        };
+
+        let update = match &self.data {
+            Data::Struct(fields)
+                if self.borrowed().is_none()
+                    && self.build_with.is_none()
+                    && self.try_build_with.is_none() =>
+            {
+                self.generate_update(&fields.fields)?
+            }
+            _ => quote!(),
+        };
+
+        let from_row_body = match self.delegated_panic() {
+            Some(message) if self.inherits_rename_all() => {
+                quote!(Self::try_from_row_with_rename_all(row, prefix, rename_all).expect(#message))
+            }
+            Some(message) => quote!(Self::try_from_row_with_prefix(row, prefix).expect(#message)),
+            None => from_row_body,
+        };
+
+        let from_row_trait = self.trait_path();
+        let lifetime = self.borrowed();
+
+        let methods = if self.inherits_rename_all() {
+            quote! {
+                fn from_row_with_prefix(row: &#lifetime postgres_from_row::tokio_postgres::Row, prefix: &str) -> Self {
+                    Self::from_row_with_rename_all(row, prefix, None)
+                }
+
+                fn try_from_row_with_prefix(row: &#lifetime postgres_from_row::tokio_postgres::Row, prefix: &str) -> std::result::Result<Self, postgres_from_row::tokio_postgres::Error> {
+                    Self::try_from_row_with_rename_all(row, prefix, None)
+                }
+
+                fn from_row_with_rename_all(row: &#lifetime postgres_from_row::tokio_postgres::Row, prefix: &str, rename_all: std::option::Option<postgres_from_row::RenameRule>) -> Self {
+                    #from_row_body
+                }
+
+                fn try_from_row_with_rename_all(row: &#lifetime postgres_from_row::tokio_postgres::Row, prefix: &str, rename_all: std::option::Option<postgres_from_row::RenameRule>) -> std::result::Result<Self, postgres_from_row::tokio_postgres::Error> {
+                    #try_from_row_body
+                }
+            }
+        } else {
+            quote! {
+                fn from_row_with_prefix(row: &#lifetime postgres_from_row::tokio_postgres::Row, prefix: &str) -> Self {
+                    #from_row_body
+                }
+
+                fn try_from_row_with_prefix(row: &#lifetime postgres_from_row::tokio_postgres::Row, prefix: &str) -> std::result::Result<Self, postgres_from_row::tokio_postgres::Error> {
+                    #try_from_row_body
+                }
+            }
+        };
+
+        let feature_checks = self.generate_feature_checks();
+
+        let tokens = quote! {
+            impl #impl_generics #from_row_trait for #self_ty where #(#predicates,)* {
+                #columns
+
+                #methods
+
+                #indexed
+
+                #verbose
+
+                #detailed
+
+                #validate_columns
+
+                #update
+            }
+
+            #indexed_marker
+
+            #sqlx
+
+            #composite
+
+            #feature_checks
+        };
+
+        Ok(self.with_crate(tokens).into())
+    }
+
+    /// Generate the `FromSql` implementation for composite types, which reads every field from the field of the
+    /// composite type with the same name.
+    fn generate_composite(&self) -> Result<TokenStream2> {
+        let fields = self.fields();
+
+        let lifetime = self.generated_lifetime();
+        let mut generics = self.generics.clone();
+        generics.params.insert(0, syn::parse_quote!(#lifetime));
+
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let self_ty = self.self_ty();
+
+        let mut predicates = Vec::new();
+        let mut members = Vec::new();
+        let mut values = Vec::new();
+        let mut accepts = Vec::new();
+
+        for (index, field) in fields.iter().enumerate() {
+            let ty = &field.ty;
+            members.push(field.member(index));
+
+            if field.skip {
+                predicates.push(quote!(#ty: std::default::Default));
+                values.push(quote!(std::default::Default::default()));
+                continue;
+            }
+
+            let name = match (&field.rename, &field.ident) {
+                (Some(rename), _) => rename.0[0].clone(),
+                (None, Some(ident)) => match self.rename_all {
+                    Some(rule) => rule.apply(&ident.to_string()),
+                    None => ident.to_string(),
+                },
+                (None, None) => unreachable!("composite structs have named fields"),
+            };
+
+            predicates
+                .push(quote!(#ty: postgres_from_row::tokio_postgres::types::FromSql<#lifetime>));
+
+            let get = quote!(composite.get::<#ty>(#name)?);
+            let default = field.default_value(self)?;
+            let optional = default.is_some();
+
+            if field.defaults(self) {
+                predicates.push(quote!(#ty: std::default::Default));
+            }
+
+            values.push(match default {
+                Some(default) => quote!(if composite.has(#name) { #get } else { #default }),
+                None => get,
+            });
+
+            accepts.push(
+                quote!(postgres_from_row::__private::accepts_field::<#ty>(ty, #name, #optional)),
+            );
+        }
+
+        let predicates = merge_predicates(where_clause, predicates);
+        let (original_impl_generics, _, original_where_clause) = self.generics.split_for_impl();
+
+        Ok(quote! {
+            impl #original_impl_generics postgres_from_row::__private::FromComposite for #self_ty #original_where_clause {}
+
+            impl #impl_generics postgres_from_row::tokio_postgres::types::FromSql<#lifetime> for #self_ty where #(#predicates,)* {
+                fn from_sql(ty: &postgres_from_row::tokio_postgres::types::Type, raw: &#lifetime [u8]) -> std::result::Result<Self, std::boxed::Box<dyn std::error::Error + std::marker::Sync + std::marker::Send>> {
+                    let composite = postgres_from_row::__private::Composite::new(ty, raw)?;
+
+                    Ok(Self { #(#members: #values),* })
+                }
+
+                fn accepts(ty: &postgres_from_row::tokio_postgres::types::Type) -> bool {
+                    postgres_from_row::__private::is_composite(ty) #(&& #accepts)*
+                }
+            }
+        })
+    }
+
+    /// Generate the `FromSql` implementation of `FromRowScalar`, which reads the single field of a newtype with the
+    /// `FromSql` implementation of that field.
+    fn generate_scalar(self) -> Result<TokenStream> {
+        let field = match &self.data {
+            Data::Struct(fields) if fields.len() == 1 => &fields.fields[0],
+            _ => {
+                return Err(Error::custom(
+                    "`FromRowScalar` can only be derived for structs with exactly one field",
+                )
+                .with_span(&self.ident)
+                .into())
+            }
+        };
+
+        if let Some(attribute) = field.attributes().first() {
+            return Err(Error::custom(format!(
+                "`{attribute}` can't be used with `FromRowScalar`, the field is always read as is"
+            ))
+            .with_span(&field.ty)
+            .into());
+        }
+
+        let lifetime = self.generated_lifetime();
+        let mut generics = self.generics.clone();
+        generics.params.insert(0, syn::parse_quote!(#lifetime));
+
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let self_ty = self.self_ty();
+        let ty = &field.ty;
+        let member = field.member(0);
+
+        let predicates = merge_predicates(
+            where_clause,
+            vec![quote!(#ty: postgres_from_row::tokio_postgres::types::FromSql<#lifetime>)],
+        );
+
+        let tokens = quote! {
+            impl #impl_generics postgres_from_row::tokio_postgres::types::FromSql<#lifetime> for #self_ty where #(#predicates,)* {
+                fn from_sql(ty: &postgres_from_row::tokio_postgres::types::Type, raw: &#lifetime [u8]) -> std::result::Result<Self, std::boxed::Box<dyn std::error::Error + std::marker::Sync + std::marker::Send>> {
+                    Ok(Self { #member: <#ty as postgres_from_row::tokio_postgres::types::FromSql<#lifetime>>::from_sql(ty, raw)? })
+                }
+
+                fn from_sql_null(ty: &postgres_from_row::tokio_postgres::types::Type) -> std::result::Result<Self, std::boxed::Box<dyn std::error::Error + std::marker::Sync + std::marker::Send>> {
+                    Ok(Self { #member: <#ty as postgres_from_row::tokio_postgres::types::FromSql<#lifetime>>::from_sql_null(ty)? })
+                }
+
+                fn accepts(ty: &postgres_from_row::tokio_postgres::types::Type) -> bool {
+                    <#ty as postgres_from_row::tokio_postgres::types::FromSql<#lifetime>>::accepts(ty)
+                }
+            }
+        };
+
+        Ok(self.with_crate(tokens).into())
+    }
+
+    /// Generate the `sqlx::FromRow` implementation for postgres rows, which reads the same columns as `from_row`.
+    fn generate_sqlx(&self) -> Result<TokenStream2> {
+        let fields = self.fields();
+
+        let lifetime = self.generated_lifetime();
+        let mut generics = self.generics.clone();
+        generics.params.insert(0, syn::parse_quote!(#lifetime));
+
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let self_ty = self.self_ty();
+
+        let mut predicates = Vec::new();
+
+        for field in fields {
+            field.add_sqlx_predicates(self, &mut predicates)?;
+        }
+
+        let values = fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| field.generate_sqlx(self, index))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let construct = self.construct(quote!(Self), fields, &values)?;
+        let predicates = merge_predicates(where_clause, predicates);
+
+        Ok(quote! {
+            impl #impl_generics postgres_from_row::sqlx::FromRow<#lifetime, postgres_from_row::sqlx::postgres::PgRow> for #self_ty where #(#predicates,)* {
+                fn from_row(row: &#lifetime postgres_from_row::sqlx::postgres::PgRow) -> std::result::Result<Self, postgres_from_row::sqlx::Error> {
+                    Ok(#construct)
+                }
+            }
+        })
+    }
+
+    /// Generates the values of `fields` for both `from_row` and `try_from_row`.
+    ///
+    /// `slots` contains the position of each field in the `indices` passed to `from_row_indexed`, fields without a
+    /// slot are looked up normally. `owner` is the path of the struct or variant the fields belong to, as it's
+    /// shown in panic messages.
+    fn generate_fields(
+        &self,
+        fields: &[FromRowField],
+        slots: &[Option<usize>],
+        owner: &str,
+    ) -> Result<(Vec<TokenStream2>, Vec<TokenStream2>)> {
+        let slot = |index: usize| slots.get(index).copied().flatten();
+
+        let from_row_fields = fields
+            .iter()
+            .enumerate()
+            .map(|(index, f)| f.generate_from_row(self, index, slot(index), owner))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let try_from_row_fields = fields
+            .iter()
+            .enumerate()
+            .map(|(index, f)| f.generate_try_from_row(self, index, slot(index), None))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        Ok((from_row_fields, try_from_row_fields))
+    }
+
+    /// Returns an expression that constructs `path` from the `values` of `fields`, either directly or by
+    /// passing them to the `build_with` function. An error of the `try_build_with` function causes a panic.
+    fn construct(
+        &self,
+        path: TokenStream2,
+        fields: &[FromRowField],
+        values: &[TokenStream2],
+    ) -> Result<TokenStream2> {
+        if let Some(build_with) = &self.build_with {
+            return Ok(quote!(#build_with(#(#values),*)));
+        }
+
+        if let Some(try_build_with) = &self.try_build_with {
+            let message = format!("could not build {}", self.ident);
+            return Ok(quote!(#try_build_with(#(#values),*).expect(#message)));
+        }
+
+        let members = fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| field.member(index));
+
+        Ok(quote!(#path { #(#members: #values),* }))
+    }
+
+    /// Returns an expression that constructs `path` like `construct`, but evaluates to a
+    /// `Result<_, tokio_postgres::Error>` which contains the error of the `try_build_with` function.
+    fn try_construct(
+        &self,
+        path: TokenStream2,
+        fields: &[FromRowField],
+        values: &[TokenStream2],
+    ) -> Result<TokenStream2> {
+        if let Some(try_build_with) = &self.try_build_with {
+            return Ok(quote!(#try_build_with(#(#values),*).map_err(std::convert::From::from)));
+        }
+
+        let construct = self.construct(path, fields, values)?;
+        Ok(quote!(std::result::Result::Ok(#construct)))
+    }
+
+    /// Generate `update_from_row_with_prefix` and `try_update_from_row_with_prefix`, which assign every field that
+    /// isn't skipped. `try_update_from_row_with_prefix` reads all fields before assigning any of them, so the value
+    /// is left unchanged when reading one of them fails.
+    fn generate_update(&self, fields: &[FromRowField]) -> Result<TokenStream2> {
+        let (from_row_values, try_from_row_values) =
+            self.generate_fields(fields, &[], &self.ident.to_string())?;
+
+        let mut members = Vec::new();
+        let mut from_row = Vec::new();
+        let mut try_from_row = Vec::new();
+
+        for (index, field) in fields.iter().enumerate() {
+            if !field.skip {
+                members.push(field.member(index));
+                from_row.push(&from_row_values[index]);
+                try_from_row.push(&try_from_row_values[index]);
+            }
+        }
+
+        let positions = (0..members.len()).map(syn::Index::from);
+        let rename_all = self.rename_all_binding();
+
+        let update = match self.delegated_panic() {
+            Some(message) => {
+                quote!(self.try_update_from_row_with_prefix(row, prefix).expect(#message))
+            }
+            None => quote! {
+                #rename_all
+
+                #(self.#members = #from_row;)*
+            },
+        };
+
+        Ok(quote! {
+            fn update_from_row_with_prefix(&mut self, row: &postgres_from_row::tokio_postgres::Row, prefix: &str) {
+                #update
+            }
+
+            fn try_update_from_row_with_prefix(&mut self, row: &postgres_from_row::tokio_postgres::Row, prefix: &str) -> std::result::Result<(), postgres_from_row::tokio_postgres::Error> {
+                #rename_all
+
+                let values = (#(#try_from_row,)*);
+                #(self.#members = values.#positions;)*
+
+                std::result::Result::Ok(())
+            }
+        })
+    }
+
+    /// Generates the bodies of `from_row` and `try_from_row` for an enum, which read the tag column
+    /// and then construct the variant with the matching tag.
+    fn generate_enum(&self, variants: &[FromRowVariant]) -> Result<(TokenStream2, TokenStream2)> {
+        let tag = Column::Name(self.tag.clone().unwrap_or_default());
+        let lookup = Lookup {
+            case_insensitive: self.case_insensitive,
+            slot: None,
+        };
+
+        let tags = variants.iter().map(FromRowVariant::tag);
+        let tag_ty = quote!(postgres_from_row::__private::Tag<__FromRowTags>);
+        let get_tag = tag.get(&tag_ty, lookup, &format!("the tag of {}", self.ident));
+        let try_get_tag = tag.read(quote!(try_get), &tag_ty, lookup);
+
+        let tags = quote! {
+            struct __FromRowTags;
+
+            impl postgres_from_row::__private::Tags for __FromRowTags {
+                const TAGS: &'static [&'static str] = &[#(#tags),*];
+            }
+        };
+
+        let mut from_row_arms = Vec::new();
+        let mut try_from_row_arms = Vec::new();
+
+        for (index, variant) in variants.iter().enumerate() {
+            let ident = &variant.ident;
+            let fields = &variant.fields.fields;
+            let owner = format!("{}::{ident}", self.ident);
+            let (from_row_values, try_from_row_values) =
+                self.generate_fields(fields, &[], &owner)?;
+            let from_row = self.construct(quote!(Self::#ident), fields, &from_row_values)?;
+            let try_from_row =
+                self.construct(quote!(Self::#ident), fields, &try_from_row_values)?;
+
+            from_row_arms.push(quote!(#index => #from_row));
+            try_from_row_arms.push(quote!(#index => #try_from_row));
+        }
+
+        Ok((
+            quote! {
+                #tags
+
+                match #get_tag.0 {
+                    #(#from_row_arms,)*
+                    _ => unreachable!(),
+                }
+            },
+            quote! {
+                #tags
+
+                Ok(match #try_get_tag?.0 {
+                    #(#try_from_row_arms,)*
+                    _ => unreachable!(),
+                })
+            },
+        ))
+    }
+
+    /// Generate `try_from_row_verbose_with_prefix`, which reads every field into a local before
+    /// constructing the struct, collecting the errors of all fields that failed.
+    fn generate_verbose(&self) -> Result<TokenStream2> {
+        let fields = self.fields();
+        let mut locals = Vec::new();
+        let mut values = Vec::new();
+
+        // Runs `value` in a closure, so its `?` operators only return from reading this field.
+        let attempt = |value: TokenStream2| {
+            quote! {
+                (|| {
+                    let value = #value;
+                    Ok::<_, postgres_from_row::tokio_postgres::Error>(value)
+                })()
+            }
+        };
+
+        for (index, field) in fields.iter().enumerate() {
+            let local = quote::format_ident!("__field_{}", index);
+            let label = field.error_label(self, index);
+            let failed = quote! {
+                Err(error) => {
+                    errors.push((#label, error));
+                    None
+                }
+            };
+
+            let read = if field.flatten && field.null_check.is_none() && !field.inherit_rename_all {
+                let target_ty = field.target_ty()?;
+                let prefix = field.flatten_prefix();
+                let attempt =
+                    attempt(field.generate_try_from_row(self, index, None, Some(quote!(value)))?);
+
+                quote! {
+                    match <#target_ty as postgres_from_row::FromRow>::try_from_row_verbose_with_prefix(row, #prefix) {
+                        Ok(value) => match #attempt {
+                            Ok(value) => Some(value),
+                            #failed
+                        },
+                        Err(nested) => {
+                            errors.extend(nested);
+                            None
+                        }
+                    }
+                }
+            } else {
+                let attempt = attempt(field.generate_try_from_row(self, index, None, None)?);
+
+                quote! {
+                    match #attempt {
+                        Ok(value) => Some(value),
+                        #failed
+                    }
+                }
+            };
+
+            locals.push(quote!(let #local = #read;));
+            values.push(quote!(#local.unwrap()));
+        }
+
+        let construct = match &self.try_build_with {
+            Some(try_build_with) => quote! {
+                #try_build_with(#(#values),*).map_err(|error| std::vec![(std::string::String::new(), std::convert::From::from(error))])
+            },
+            None => {
+                let construct = self.construct(quote!(Self), fields, &values)?;
+                quote!(Ok(#construct))
+            }
+        };
+        let rename_all = self.rename_all_binding();
+
+        Ok(quote! {
+            fn try_from_row_verbose_with_prefix(row: &postgres_from_row::tokio_postgres::Row, prefix: &str) -> std::result::Result<Self, std::vec::Vec<(std::string::String, postgres_from_row::tokio_postgres::Error)>> {
+                #rename_all
+                let mut errors = std::vec::Vec::new();
+
+                #(#locals)*
+
+                if !errors.is_empty() {
+                    return Err(errors);
+                }
+
+                #construct
+            }
+        })
+    }
+
+    /// Generate `try_from_row_detailed_with_prefix`, which returns the error of the first field that fails
+    /// together with its column and field name.
+    fn generate_detailed(&self) -> Result<TokenStream2> {
+        let fields = self.fields();
+        let mut locals = Vec::new();
+        let mut values = Vec::new();
+
+        for (index, field) in fields.iter().enumerate() {
+            let local = quote::format_ident!("__field_{}", index);
+            let name = field.display_name(index);
+            let column = if field.reads_column() {
+                field.error_label(self, index)
+            } else {
+                quote!(std::string::String::new())
+            };
+
+            // Runs `value` in a closure, so its `?` operators only return from reading this field.
+            let attempt = |value: TokenStream2| {
+                quote! {
+                    match (|| {
+                        let value = #value;
+                        Ok::<_, postgres_from_row::tokio_postgres::Error>(value)
+                    })() {
+                        Ok(value) => value,
+                        Err(error) => return Err(postgres_from_row::__private::detailed(#column, #name, error)),
+                    }
+                }
+            };
+
+            let read = if field.flatten && field.null_check.is_none() && !field.inherit_rename_all {
+                let target_ty = field.target_ty()?;
+                let prefix = field.flatten_prefix();
+                let attempt =
+                    attempt(field.generate_try_from_row(self, index, None, Some(quote!(value)))?);
+
+                quote! {{
+                    let value = <#target_ty as postgres_from_row::FromRow>::try_from_row_detailed_with_prefix(row, #prefix)?;
+                    #attempt
+                }}
+            } else {
+                attempt(field.generate_try_from_row(self, index, None, None)?)
+            };
+
+            locals.push(quote!(let #local = #read;));
+            values.push(quote!(#local));
+        }
+
+        let construct = match &self.try_build_with {
+            Some(try_build_with) => quote! {
+                #try_build_with(#(#values),*).map_err(|error| postgres_from_row::FromRowError::from(std::convert::Into::<postgres_from_row::tokio_postgres::Error>::into(error)))
+            },
+            None => {
+                let construct = self.construct(quote!(Self), fields, &values)?;
+                quote!(Ok(#construct))
+            }
+        };
+        let rename_all = self.rename_all_binding();
+
+        Ok(quote! {
+            fn try_from_row_detailed_with_prefix(row: &postgres_from_row::tokio_postgres::Row, prefix: &str) -> std::result::Result<Self, postgres_from_row::FromRowError> {
+                #rename_all
+
+                #(#locals)*
+
+                #construct
+            }
+        })
+    }
+
+    /// Generate `validate_columns_with_prefix`, which checks the columns of this struct that are required and read
+    /// by name, followed by the columns of flattened fields.
+    ///
+    /// Columns that have a default, are read by position or are named by an inherited convention aren't checked.
+    fn generate_validate_columns(&self) -> Result<TokenStream2> {
+        let mut expected = Vec::new();
+        let mut nested = Vec::new();
+
+        for (index, field) in self.fields().iter().enumerate() {
+            if field.flatten {
+                if field.null_check.is_some() || field.inherit_rename_all {
+                    continue;
+                }
+
+                let target_ty = field.target_ty()?;
+                let prefix = field.flatten_prefix();
+
+                nested.push(quote! {
+                    <#target_ty as postgres_from_row::FromRow>::validate_columns_with_prefix(columns, #prefix)?;
+                });
+                continue;
+            }
+
+            if !field.reads_column() || field.default_value(self)?.is_some() {
+                continue;
+            }
+
+            match field.column(self, index) {
+                Column::Name(name) | Column::Inherited { name, .. } => {
+                    expected.push(quote!(&[#name]));
+                }
+                Column::Const(path) => expected.push(quote!(&[#path])),
+                Column::Candidates(names) => expected.push(quote!(&[#(#names),*])),
+                Column::Index(_) => {}
+            }
+        }
+
+        let case_insensitive = self.case_insensitive;
+
+        Ok(quote! {
+            fn validate_columns_with_prefix(columns: &[postgres_from_row::tokio_postgres::Column], prefix: &str) -> std::result::Result<(), postgres_from_row::ColumnError> {
+                postgres_from_row::__private::validate_columns(columns, prefix, &[#(#expected),*], #case_insensitive)?;
+                #(#nested)*
+
+                Ok(())
+            }
+        })
+    }
+
+    /// Generate the `COLUMNS` constant, listing every column that is read by name followed by the columns
+    /// of flattened fields.
+    ///
+    /// The columns of flattened fields can't be referenced from a generic struct, so the default is kept there.
+    fn generate_columns(&self) -> Result<TokenStream2> {
+        let mut names = Vec::new();
+        let mut nested = Vec::new();
+
+        let fields: Vec<_> = match &self.data {
+            Data::Struct(fields) => fields.fields.iter().enumerate().collect(),
+            Data::Enum(variants) => {
+                names.extend(self.tag.iter().map(|tag| quote!(#tag)));
+
+                variants
+                    .iter()
+                    .flat_map(|variant| variant.fields.fields.iter().enumerate())
+                    .collect()
+            }
+        };
+
+        for (index, field) in fields {
+            if field.flatten {
+                let prefix = field.prefix.clone().unwrap_or_default();
+                let target_ty = field.target_ty()?;
+                let from_row_trait = field.trait_path(self);
+
+                nested.push(quote!((#prefix, <#target_ty as #from_row_trait>::COLUMNS)));
+                continue;
+            }
+
+            if !field.reads_column() {
+                continue;
+            }
+
+            if let Some(name) = field.column(self, index).name_tokens() {
+                if !names
+                    .iter()
+                    .any(|other| other.to_string() == name.to_string())
+                {
+                    names.push(name);
+                }
+            }
+        }
+
+        if !nested.is_empty() && !self.generics.params.is_empty() {
+            return Ok(quote!());
+        }
+
+        if self
+            .all_fields()
+            .iter()
+            .any(|field| field.inherit_rename_all)
+        {
+            return Ok(quote!());
+        }
+
+        let columns = columns_const(&names, &nested);
+
+        Ok(quote!(const COLUMNS: &'static [&'static str] = #columns;))
+    }
+
+    /// Generate the `ToRowParams` implementation, which passes every field that is read from a named column
+    /// as a parameter, followed by the parameters of flattened fields.
+    fn generate_to_row_params(self) -> Result<TokenStream> {
+        self.validate()?;
+
+        let Data::Struct(fields) = &self.data else {
+            return Err(Error::custom("`ToRowParams` can only be derived for structs").into());
+        };
+
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+        let self_ty = self.self_ty();
+
+        let mut predicates = Vec::new();
+        let mut names = Vec::new();
+        let mut params = Vec::new();
+        let mut nested = Vec::new();
+        let mut nested_params = Vec::new();
+
+        for (index, field) in fields.fields.iter().enumerate() {
+            let member = field.member(index);
+            let ty = &field.ty;
+
+            if field.flatten {
+                if field.null_check.is_some() || field.wrapper().is_some() {
+                    return Err(field
+                        .error("flattened fields with `null_check` or `wrap` can't be converted to parameters")
+                        .into());
+                }
+
+                let prefix = field.prefix.clone().unwrap_or_default();
+
+                predicates.push(quote!(#ty: postgres_from_row::ToRowParams));
+                nested.push(
+                    quote!((#prefix, <#ty as postgres_from_row::ToRowParams>::PARAM_COLUMNS)),
+                );
+                nested_params.push(
+                    quote!(params.extend(postgres_from_row::ToRowParams::params(&self.#member));),
+                );
+                continue;
+            }
+
+            if !field.reads_column() {
+                continue;
+            }
+
+            let column = field.column(&self, index);
+            let Some(name) = column.name_tokens() else {
+                return Err(field
+                    .error("fields that are read by position can't be converted to parameters")
+                    .into());
+            };
+
+            predicates.push(
+                quote!(#ty: postgres_from_row::tokio_postgres::types::ToSql + std::marker::Sync),
+            );
+            names.push(name);
+            params.push(quote!(params.push(&self.#member);));
+        }
+
+        if !nested.is_empty() && !self.generics.params.is_empty() {
+            return Err(Error::custom(
+                "`ToRowParams` can't be derived for generic structs with flattened fields",
+            )
+            .into());
+        }
+
+        let columns = columns_const(&names, &nested);
+        let predicates = merge_predicates(where_clause, predicates);
+
+        let tokens = quote! {
+            impl #impl_generics postgres_from_row::ToRowParams for #self_ty where #(#predicates,)* {
+                const PARAM_COLUMNS: &'static [&'static str] = #columns;
+
+                fn params(&self) -> std::vec::Vec<&(dyn postgres_from_row::tokio_postgres::types::ToSql + std::marker::Sync)> {
+                    let mut params: std::vec::Vec<&(dyn postgres_from_row::tokio_postgres::types::ToSql + std::marker::Sync)> = std::vec::Vec::new();
+
+                    #(#params)*
+                    #(#nested_params)*
+
+                    params
+                }
+            }
+        };
+
+        Ok(self.with_crate(tokens).into())
+    }
+
+    /// Generate the `FromGroupedRows` implementation, which starts a new value whenever the fields with `group_key`
+    /// change between consecutive rows, and otherwise adds the child of the row to the `children` of the last value.
+    ///
+    /// The generated code binds the child of every row to `child`, which the value of the `children` field is
+    /// collected from.
+    fn generate_grouped(self) -> Result<TokenStream> {
+        self.validate()?;
+
+        let Data::Struct(fields) = &self.data else {
+            return Err(Error::custom("`FromGroupedRows` can only be derived for structs").into());
+        };
+
+        if let Some(lifetime) = self.borrowed() {
+            return Err(Error::custom(
+                "`FromGroupedRows` can't be derived for types that borrow from the row",
+            )
+            .with_span(&lifetime)
+            .into());
+        }
+
+        let mut children = fields
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.children);
+
+        if let Some(field) = fields.fields.iter().find(|field| field.rest) {
+            return Err(field
+                .error("`#[from_row(rest)]` is not supported by `FromGroupedRows`")
+                .into());
+        }
+
+        let Some((children_index, children_field)) = children.next() else {
+            return Err(Error::custom(
+                "`FromGroupedRows` requires a `Vec<T>` field with `#[from_row(children)]`",
+            )
+            .into());
+        };
+
+        if let Some((_, other)) = children.next() {
+            return Err(other
+                .error("only a single field can have `#[from_row(children)]`")
+                .into());
+        }
+
+        let keys = fields
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.group_key)
+            .collect::<Vec<_>>();
+
+        if keys.is_empty() {
+            return Err(Error::custom(
+                "`FromGroupedRows` requires at least one field with `#[from_row(group_key)]`",
+            )
+            .into());
+        }
+
+        let owner = self.ident.to_string();
+        let key_members = keys.iter().map(|(index, field)| field.member(*index));
+        let key_positions = (0..keys.len()).map(syn::Index::from);
+        let from_row_keys = keys
+            .iter()
+            .map(|(index, field)| field.generate_from_row(&self, *index, None, &owner))
+            .collect::<syn::Result<Vec<_>>>()?;
+        let try_from_row_keys = keys
+            .iter()
+            .map(|(index, field)| field.generate_try_from_row(&self, *index, None, None))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let children_member = children_field.member(children_index);
+        let label = format!(
+            "field {owner}::{}",
+            children_field.display_name(children_index)
+        );
+        let from_row_child = children_field.generate_child(&self, Some(&label))?;
+        let try_from_row_child = children_field.generate_child(&self, None)?;
+
+        let (from_row_values, try_from_row_values) =
+            self.generate_fields(&fields.fields, &[], &owner)?;
+        let from_row = self.construct(quote!(Self), &fields.fields, &from_row_values)?;
+        let try_from_row =
+            self.try_construct(quote!(Self), &fields.fields, &try_from_row_values)?;
+
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+        let self_ty = self.self_ty();
+        let predicates = merge_predicates(where_clause, self.predicates()?);
+        let rename_all = self.rename_all_binding();
+        let key_matches = quote!(#(last.#key_members == key.#key_positions)&&*);
+        let feature_checks = self.generate_feature_checks();
+
+        let tokens = quote! {
+            impl #impl_generics postgres_from_row::FromGroupedRows for #self_ty where #(#predicates,)* {
+                fn from_grouped_rows(rows: &[postgres_from_row::tokio_postgres::Row]) -> std::vec::Vec<Self> {
+                    let mut grouped: std::vec::Vec<Self> = std::vec::Vec::new();
+
+                    for row in rows {
+                        let prefix = "";
+                        #rename_all
+
+                        let key = (#(#from_row_keys,)*);
+                        let child = #from_row_child;
+
+                        match grouped.last_mut() {
+                            Some(last) if #key_matches => {
+                                std::iter::Extend::extend(&mut last.#children_member, child)
+                            }
+                            _ => grouped.push(#from_row),
+                        }
+                    }
+
+                    grouped
+                }
+
+                fn try_from_grouped_rows(rows: &[postgres_from_row::tokio_postgres::Row]) -> std::result::Result<std::vec::Vec<Self>, postgres_from_row::tokio_postgres::Error> {
+                    let mut grouped: std::vec::Vec<Self> = std::vec::Vec::new();
+
+                    for row in rows {
+                        let prefix = "";
+                        #rename_all
+
+                        let key = (#(#try_from_row_keys,)*);
+                        let child = #try_from_row_child;
+
+                        match grouped.last_mut() {
+                            Some(last) if #key_matches => {
+                                std::iter::Extend::extend(&mut last.#children_member, child)
+                            }
+                            _ => grouped.push(#try_from_row?),
+                        }
+                    }
+
+                    std::result::Result::Ok(grouped)
+                }
+            }
+
+            #feature_checks
+        };
+
+        Ok(self.with_crate(tokens).into())
+    }
+
+    /// Returns the message of the single panic in the methods that can't fail with `#[from_row(panic_via_try)]`,
+    /// which then call their fallible counterpart.
+    fn delegated_panic(&self) -> Option<String> {
+        self.panic_via_try
+            .then(|| format!("from_row failed for {}", self.ident))
+    }
+
+    /// Returns the lifetime used by the generated code that isn't tied to the type, which is `'__from_row_lifetime`
+    /// unless it's overridden with `#[from_row(lifetime = "..")]`.
+    fn generated_lifetime(&self) -> syn::Lifetime {
+        match &self.lifetime {
+            Some(LifetimeName(lifetime)) => lifetime.clone(),
+            None => syn::parse_quote!('__from_row_lifetime),
+        }
+    }
+
+    /// Returns the type the traits are implemented for, which is the remote type of `#[from_row(remote = "..")]`
+    /// or the type the derive is applied to.
+    fn self_ty(&self) -> TokenStream2 {
+        match &self.remote {
+            Some(remote) => remote.to_token_stream(),
+            None => {
+                let ident = &self.ident;
+                let (_, ty_generics, _) = self.generics.split_for_impl();
+                quote!(#ident #ty_generics)
+            }
+        }
+    }
+
+    /// Wraps the generated `tokens` in an anonymous constant that imports the path of `#[from_row(crate = "..")]`
+    /// as `postgres_from_row`, which all generated code refers to.
+    ///
+    /// The types generated for the delimiters of `#[from_row(split = "..")]` are defined in the same constant,
+    /// so they can be named in the where clause without being visible outside of it.
+    /// Generate a check for every field with an attribute that requires a feature, which fails to compile with a
+    /// message naming the feature when it isn't enabled.
+    fn generate_feature_checks(&self) -> TokenStream2 {
+        let checks = self.all_fields().into_iter().filter_map(|field| {
+            let (feature, attribute) = field.required_feature()?;
+            let check = quote::format_ident!("__from_row_requires_{}", feature);
+            Some(quote::quote_spanned!(field.span()=> postgres_from_row::#check!(#attribute);))
+        });
+
+        quote!(#(#checks)*)
+    }
+
+    fn with_crate(&self, tokens: TokenStream2) -> TokenStream2 {
+        let mut delimiters: Vec<&Delimiter> = Vec::new();
+
+        for delimiter in self
+            .all_fields()
+            .into_iter()
+            .filter_map(|field| field.split.as_ref())
+        {
+            if delimiters.iter().all(|other| other.0 != delimiter.0) {
+                delimiters.push(delimiter);
+            }
+        }
+
+        let delimiters = delimiters.into_iter().map(Delimiter::definition);
+
+        match &self.krate {
+            Some(krate) => quote! {
+                const _: () = {
+                    use #krate as postgres_from_row;
+
+                    #(#delimiters)*
+
+                    #tokens
+                };
+            },
+            None if !self.all_fields().iter().any(|field| field.split.is_some()) => tokens,
+            None => quote! {
+                const _: () = {
+                    #(#delimiters)*
+
+                    #tokens
+                };
+            },
+        }
+    }
+
+    /// Generate `resolve_indices`, `from_row_indexed` and `try_from_row_indexed`.
+    ///
+    /// Every field that reads a column by name gets a slot in the resolved `ColumnIndices`,
+    /// all other fields are read the same way as in `from_row`.
+    fn generate_indexed(&self) -> Result<TokenStream2> {
+        let mut names = Vec::new();
+        let mut slots = Vec::new();
+
+        for (index, field) in self.fields().iter().enumerate() {
+            match field.column(self, index) {
+                Column::Name(name) | Column::Inherited { name, .. } if field.reads_column() => {
+                    slots.push(Some(names.len()));
+                    names.push(quote!(#name));
+                }
+                Column::Const(path) if field.reads_column() => {
+                    slots.push(Some(names.len()));
+                    names.push(quote!(#path));
+                }
+                _ => slots.push(None),
+            }
+        }
+
+        let (from_row_values, try_from_row_values) =
+            self.generate_fields(self.fields(), &slots, &self.ident.to_string())?;
+        let from_row = self.construct(quote!(Self), self.fields(), &from_row_values)?;
+        let try_from_row = self.try_construct(quote!(Self), self.fields(), &try_from_row_values)?;
+
+        let case_insensitive = self.case_insensitive;
+        let rename_all = self.rename_all_binding();
+
+        let from_row_indexed = match self.delegated_panic() {
+            Some(message) => quote!(Self::try_from_row_indexed(row, indices).expect(#message)),
+            None => quote! {
+                let prefix = "";
+                #rename_all
+
+                #from_row
+            },
+        };
+
+        Ok(quote! {
+            fn resolve_indices(columns: &[postgres_from_row::tokio_postgres::Column]) -> postgres_from_row::ColumnIndices {
+                postgres_from_row::__private::resolve_indices(columns, &[#(#names),*], #case_insensitive)
+            }
+
+            fn from_row_indexed(row: &postgres_from_row::tokio_postgres::Row, indices: &postgres_from_row::ColumnIndices) -> Self {
+                #from_row_indexed
+            }
+
+            fn try_from_row_indexed(row: &postgres_from_row::tokio_postgres::Row, indices: &postgres_from_row::ColumnIndices) -> std::result::Result<Self, postgres_from_row::tokio_postgres::Error> {
+                let prefix = "";
+                #rename_all
+
+                #try_from_row
+            }
+        })
+    }
+}
+
+/// A single variant of an enum that derives `FromRow`.
+#[derive(Debug, FromVariant)]
+#[darling(attributes(from_row), forward_attrs(allow, doc, cfg))]
+struct FromRowVariant {
+    /// The identifier of this variant.
+    ident: syn::Ident,
+    /// The fields of this variant, which are read the same way as the fields of a struct.
+    fields: Fields<FromRowField>,
+    /// Override the value of the tag column that selects this variant instead of using `self.ident`.
+    rename: Option<String>,
+}
+
+impl FromRowVariant {
+    /// Returns the value of the tag column that selects this variant.
+    fn tag(&self) -> String {
+        self.rename
+            .clone()
+            .unwrap_or_else(|| self.ident.to_string())
+    }
+}
+
+/// A single field inside of a struct that derives `FromRow`
+#[derive(Debug, FromField)]
+#[darling(attributes(from_row), forward_attrs(allow, doc, cfg))]
+struct FromRowField {
+    /// The identifier of this field, or `None` when this field is part of a tuple struct.
+    ident: Option<syn::Ident>,
+    /// The type specified in this field.
+    ty: syn::Type,
+    /// Wether to flatten this field. Flattening means calling the `FromRow` implementation
+    /// of `self.ty` instead of extracting it directly from the row.
+    #[darling(default)]
+    flatten: bool,
+    /// Optionaly use this type as the target for `FromRow` or `FromSql`, and then
+    /// call `TryFrom::try_from` to convert it the `self.ty`.
+    #[darling(default, with = parse_type)]
+    try_from: Option<syn::Type>,
+    /// Optionaly use this type as the target for `FromRow` or `FromSql`, and then
+    /// call `From::from` to convert it the `self.ty`.
+    #[darling(default, with = parse_type)]
+    from: Option<syn::Type>,
+    /// Optionaly use this function to convert the value extracted from the row into `self.ty`.
+    /// The function must have the signature `fn(R) -> T`, where `R` implements `FromSql`.
+    #[darling(default, with = parse_function)]
+    from_fn: Option<syn::ExprPath>,
+    /// Optionaly use this fallible function to convert the value extracted from the row into `self.ty`.
+    /// The function must have the signature `fn(R) -> Result<T, E>`, where `R` implements `FromSql`
+    /// and `postgres_from_row::tokio_postgres::Error: From<E>`.
+    #[darling(default, with = parse_function)]
+    try_from_fn: Option<syn::ExprPath>,
+    /// Optionaly read the column as a `String` and use this function to deserialize it into `self.ty`.
+    /// The function must have the signature `fn(&str) -> Result<T, E>`, just like `try_from_fn` the error is
+    /// converted with `postgres_from_row::tokio_postgres::Error: From<E>`.
+    #[darling(default, with = parse_function)]
+    deserialize_with: Option<syn::ExprPath>,
+    /// Optionaly read a PostGIS `geometry` or `geography` column, or a `bytea` column, as its EWKB bytes and use this
+    /// function to decode them into `self.ty`. The function must have the signature `fn(&[u8]) -> Result<T, E>`,
+    /// the error is converted like the error of `deserialize_with`.
+    #[darling(default, with = parse_function)]
+    wkb: Option<syn::ExprPath>,
+    /// Optionaly use the functions in this module to read the column, serde's `with` style.
+    /// The module must provide `fn from_row_value(&Row, &str) -> T` and
+    /// `fn try_from_row_value(&Row, &str) -> Result<T, postgres_from_row::tokio_postgres::Error>`,
+    /// which are called with the row and the column name.
+    with: Option<syn::Path>,
+    /// Optionaly use this function to extract the value from the row, instead of reading a single column.
+    /// The function must have the signature `fn(&Row) -> T`.
+    from_row_fn: Option<syn::ExprPath>,
+    /// Optionaly use this fallible function to extract the value from the row, instead of reading a single column.
+    /// The function must have the signature `fn(&Row) -> Result<T, postgres_from_row::tokio_postgres::Error>`.
+    try_from_row_fn: Option<syn::ExprPath>,
+    /// Optionaly read the type inside of this smart pointer, and then call `From::from` to wrap it into `self.ty`.
+    /// Unsized types are read as their owned counterpart, so `Arc<str>` is read as a `String`.
+    wrap: Option<Wrapper>,
+    /// Optionaly read the column as this integer type, `i64` by default, and convert it with the
+    /// `from_bits_truncate` function of a `bitflags` type.
+    bit_flags: Option<IntegerType>,
+    /// Optionaly read the column as this integer type, `i64` by default, and convert it into `self.ty` with `TryFrom`,
+    /// for unsigned fields that postgres has no column type for. An `Option<T>` field is `None` when the column is `NULL`.
+    cast: Option<IntegerType>,
+    /// Wether to read a `bool` field from an `i64` column, such as a count, which is `true` when it isn't zero.
+    #[darling(default)]
+    nonzero_as_bool: bool,
+    /// Wether to read the column as `Json<T>` and unwrap it into `self.ty`, requires the `json` feature.
+    #[darling(default)]
+    json: bool,
+    /// Wether to read a text column containing JSON and deserialize it into `self.ty` with `serde_json::from_str`,
+    /// requires the `serde` feature.
+    #[darling(default)]
+    json_text: bool,
+    /// Wether to parse the text of a postgres enum or a text column into `self.ty` with its `FromStr` implementation.
+    /// An `Option<T>` field is `None` when the column is `NULL`.
+    #[darling(default)]
+    enum_text: bool,
+    /// Wether to parse a text column into a `Uuid` with `Uuid::parse_str`, for UUIDs that weren't stored as the
+    /// `uuid` type. An `Option<Uuid>` field is `None` when the column is `NULL`. Requires the `uuid` feature.
+    #[darling(default)]
+    uuid_text: bool,
+    /// Wether to read a `NUMERIC` or `MONEY` column into a `Decimal`, falling back to parsing a text column.
+    /// An `Option<Decimal>` field is `None` when the column is `NULL`. Requires the `rust_decimal` feature.
+    #[darling(default)]
+    decimal: bool,
+    /// Wether to read an `INTERVAL` column into a `Duration`, failing for negative intervals and intervals with months.
+    /// An `Option<Duration>` field is `None` when the column is `NULL`.
+    #[darling(default)]
+    interval: bool,
+    /// Wether to read a `timestamptz` or `timestamp` column into a `DateTime<Utc>`, treating a timestamp without
+    /// a time zone as UTC. Requires the `chrono` feature.
+    #[darling(default)]
+    utc: bool,
+    /// Wether to read a `hstore` column, which requires the field to be a `HashMap<String, Option<String>>`.
+    #[darling(default)]
+    hstore: bool,
+    /// Wether to read a postgres array into a `[T; N]` field, failing when the array doesn't have `N` elements.
+    #[darling(default)]
+    array: bool,
+    /// Read a text column of values separated by this delimiter into a `Vec<T>` field, parsing every value with the
+    /// `FromStr` implementation of `T`. An empty string is an empty `Vec`, an `Option<Vec<T>>` field is `None` when
+    /// the column is `NULL`.
+    split: Option<Delimiter>,
+    /// Override the name of the actual sql column instead of using `self.ident`.
+    /// When multiple names are given, the first one that is present in the row is read.
+    /// Is not compatible with `flatten` since no column is needed there.
+    rename: Option<Renames>,
+    /// Override the name of the actual sql column with the value of this `&str` constant.
+    /// Is not compatible with `rename` or `flatten`.
+    rename_const: Option<syn::Path>,
+    /// Read the column at this position instead of looking it up by name.
+    /// Is not compatible with `rename` or `flatten`.
+    #[darling(rename = "column")]
+    column_index: Option<usize>,
+    /// Prepend this prefix to all column names of a flattened field.
+    /// Can only be used in combination with `flatten`.
+    prefix: Option<String>,
+    /// Wether to read an `Option<String>` field as `None` when the column contains an empty string.
+    #[darling(default)]
+    empty_as_none: bool,
+    /// Wether to read the column as an `Option<T>` and use `T::default()` when it's `NULL`.
+    #[darling(default)]
+    null_to_default: bool,
+    /// Wether to read a `Vec<T>` field from an array of composite types, such as the result of `array_agg(row(..))`.
+    /// `T` must implement `FromSql` with `#[from_row(composite)]`.
+    #[darling(default)]
+    aggregate: bool,
+    /// Make a flattened `Option<T>` or `Option<Box<T>>` field `None` when this column of the flattened struct is
+    /// `NULL`. Can only be used in combination with `flatten`, and is required for `Option<Box<T>>`.
+    null_check: Option<String>,
+    /// Wether to pass the naming convention of the containing struct to a flattened field, naming the columns
+    /// of the flattened struct with it unless it has a convention of its own.
+    /// Can only be used in combination with `flatten`.
+    #[darling(default)]
+    inherit_rename_all: bool,
+    /// Wether to use `Default::default()` when the column is not present in the row.
+    /// Is not compatible with `flatten` since no column is needed there.
+    #[darling(default)]
+    default: bool,
+    /// Optionaly call this function to create the value when the column is not present in the row.
+    /// The function must have the signature `fn() -> T`, where `T` is `self.ty`.
+    default_fn: Option<syn::ExprPath>,
+    /// Wether to use `None` when the column is not present in the row, the field must be an `Option<T>`.
+    /// Is not compatible with `flatten` since no column is needed there.
+    #[darling(default)]
+    optional: bool,
+    /// Wether `from_row` uses `Default::default()` when the column can't be read, instead of panicking.
+    /// `try_from_row` still returns the error.
+    #[darling(default)]
+    get_or_default: bool,
+    /// Wether to skip this field entirely, it is not read from the row but initialized with `Default::default()`.
+    #[darling(default)]
+    skip: bool,
+    /// Wether this field identifies the value a row belongs to in `#[derive(FromGroupedRows)]`.
+    #[darling(default)]
+    group_key: bool,
+    /// Wether to read every column that isn't read by another field into this `HashMap<String, T>` field, keyed by
+    /// the name of the column. Only a single field of a struct can have this attribute.
+    #[darling(default)]
+    rest: bool,
+    /// Wether to collect the `T` that is read from every row of a group into this `Vec<T>` field in
+    /// `#[derive(FromGroupedRows)]`. Can be combined with `prefix` and `null_check` like a flattened field.
+    #[darling(default)]
+    children: bool,
+}
+
+/// How an attribute of a field takes part in the checks of [`FromRowField::validate`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AttributeKind {
+    /// Selects the column that is read, or the columns of a flattened field.
+    Column,
+    /// Converts the value that is read, which works for a flattened field as well.
+    Conversion,
+    /// Reads a single column in a specific way, which can't be used on a flattened field.
+    ColumnConversion,
+    /// Provides the value of a missing column, which can't be used on a flattened field.
+    Missing,
+    /// Reads the field without a column of its own, or gives it a role in `FromGroupedRows`.
+    Special,
+}
+
+/// An attribute of a field, see [`FromRowField::attribute_table`].
+struct Attribute {
+    /// Wether the attribute is set on the field.
+    present: bool,
+    /// The attribute as it's written, used in error messages.
+    name: &'static str,
+    kind: AttributeKind,
+    /// Wether the `sqlx::FromRow` implementation supports the attribute.
+    sqlx: bool,
+    /// The feature of `postgres-from-row` that the attribute requires.
+    feature: Option<&'static str>,
+}
+
+impl Attribute {
+    fn new(present: bool, name: &'static str, kind: AttributeKind) -> Self {
+        Attribute {
+            present,
+            name,
+            kind,
+            sqlx: true,
+            feature: None,
+        }
+    }
+
+    fn without_sqlx(self) -> Self {
+        Attribute {
+            sqlx: false,
+            ..self
+        }
+    }
+
+    fn requires(self, feature: &'static str) -> Self {
+        Attribute {
+            feature: Some(feature),
+            ..self
+        }
+    }
+
+    /// Wether the attribute converts the value of the field, at most one of these can be used on a field.
+    fn is_conversion(&self) -> bool {
+        matches!(
+            self.kind,
+            AttributeKind::Conversion | AttributeKind::ColumnConversion
+        )
+    }
+}
+
+impl FromRowField {
+    /// Returns every attribute a field can have, all checks on combinations of attributes are derived from this table.
+    fn attribute_table(&self) -> [Attribute; 40] {
+        [
+            Attribute::new(self.flatten, "#[from_row(flatten)]", AttributeKind::Column),
+            Attribute::new(
+                self.rename.is_some(),
+                r#"#[from_row(rename = "..")]"#,
+                AttributeKind::Column,
+            ),
+            Attribute::new(
+                self.rename_const.is_some(),
+                r#"#[from_row(rename_const = "..")]"#,
+                AttributeKind::Column,
+            ),
+            Attribute::new(
+                self.column_index.is_some(),
+                "#[from_row(column = ..)]",
+                AttributeKind::Column,
+            ),
+            Attribute::new(
+                self.prefix.is_some(),
+                r#"#[from_row(prefix = "..")]"#,
+                AttributeKind::Column,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.null_check.is_some(),
+                r#"#[from_row(null_check = "..")]"#,
+                AttributeKind::Column,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.from.is_some(),
+                r#"#[from_row(from = "..")]"#,
+                AttributeKind::Conversion,
+            ),
+            Attribute::new(
+                self.try_from.is_some(),
+                r#"#[from_row(try_from = "..")]"#,
+                AttributeKind::Conversion,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.from_fn.is_some(),
+                r#"#[from_row(from_fn = "..")]"#,
+                AttributeKind::Conversion,
+            ),
+            Attribute::new(
+                self.try_from_fn.is_some(),
+                r#"#[from_row(try_from_fn = "..")]"#,
+                AttributeKind::Conversion,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.with.is_some(),
+                r#"#[from_row(with = "..")]"#,
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.wrap.is_some(),
+                r#"#[from_row(wrap = "..")]"#,
+                AttributeKind::Conversion,
+            ),
+            Attribute::new(
+                self.bit_flags.is_some(),
+                "#[from_row(bit_flags)]",
+                AttributeKind::ColumnConversion,
+            ),
+            Attribute::new(
+                self.nonzero_as_bool,
+                "#[from_row(nonzero_as_bool)]",
+                AttributeKind::ColumnConversion,
+            ),
+            Attribute::new(
+                self.json,
+                "#[from_row(json)]",
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.json_text,
+                "#[from_row(json_text)]",
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx()
+            .requires("serde"),
+            Attribute::new(
+                self.enum_text,
+                "#[from_row(enum_text)]",
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.split.is_some(),
+                r#"#[from_row(split = "..")]"#,
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.uuid_text,
+                "#[from_row(uuid_text)]",
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx()
+            .requires("uuid"),
+            Attribute::new(
+                self.decimal,
+                "#[from_row(decimal)]",
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx()
+            .requires("rust_decimal"),
+            Attribute::new(
+                self.interval,
+                "#[from_row(interval)]",
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.cast.is_some(),
+                "#[from_row(cast)]",
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.deserialize_with.is_some(),
+                r#"#[from_row(deserialize_with = "..")]"#,
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.wkb.is_some(),
+                r#"#[from_row(wkb = "..")]"#,
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.utc,
+                "#[from_row(utc)]",
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx()
+            .requires("chrono"),
+            Attribute::new(
+                self.hstore,
+                "#[from_row(hstore)]",
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.array,
+                "#[from_row(array)]",
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.empty_as_none,
+                "#[from_row(empty_as_none)]",
+                AttributeKind::ColumnConversion,
+            ),
+            Attribute::new(
+                self.null_to_default,
+                "#[from_row(null_to_default)]",
+                AttributeKind::ColumnConversion,
+            ),
+            Attribute::new(
+                self.aggregate,
+                "#[from_row(aggregate)]",
+                AttributeKind::ColumnConversion,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.from_row_fn.is_some(),
+                r#"#[from_row(from_row_fn = "..")]"#,
+                AttributeKind::Special,
+            )
+            .without_sqlx(),
+            Attribute::new(
+                self.try_from_row_fn.is_some(),
+                r#"#[from_row(try_from_row_fn = "..")]"#,
+                AttributeKind::Special,
+            )
+            .without_sqlx(),
+            Attribute::new(self.default, "#[from_row(default)]", AttributeKind::Missing),
+            Attribute::new(
+                self.default_fn.is_some(),
+                r#"#[from_row(default_fn = "..")]"#,
+                AttributeKind::Missing,
+            ),
+            Attribute::new(
+                self.optional,
+                "#[from_row(optional)]",
+                AttributeKind::Missing,
+            ),
+            Attribute::new(
+                self.get_or_default,
+                "#[from_row(get_or_default)]",
+                AttributeKind::Missing,
+            ),
+            Attribute::new(self.skip, "#[from_row(skip)]", AttributeKind::Special),
+            Attribute::new(
+                self.group_key,
+                "#[from_row(group_key)]",
+                AttributeKind::Special,
+            ),
+            Attribute::new(
+                self.children,
+                "#[from_row(children)]",
+                AttributeKind::Special,
+            ),
+            Attribute::new(self.rest, "#[from_row(rest)]", AttributeKind::Special).without_sqlx(),
+        ]
+    }
+
+    /// Returns the attributes that are set on this field.
+    fn present_attributes(&self) -> impl Iterator<Item = Attribute> {
+        self.attribute_table()
+            .into_iter()
+            .filter(|attribute| attribute.present)
+    }
+
+    /// Returns the first attribute that converts the value of this field.
+    fn conversion(&self) -> Option<&'static str> {
+        self.present_attributes()
+            .find(Attribute::is_conversion)
+            .map(|attribute| attribute.name)
+    }
+
+    /// Checks wether this field has a valid combination of attributes
+    fn validate(&self) -> Result<()> {
+        let mut conversions = self.present_attributes().filter(Attribute::is_conversion);
+
+        if let (Some(first), Some(second)) = (conversions.next(), conversions.next()) {
+            return Err(self
+                .error(format!(
+                    "can't combine `{}` with `{}`",
+                    first.name, second.name
+                ))
+                .into());
+        }
+
+        if self.rename.is_some() && self.flatten {
+            return Err(self
+                .error(r#"can't combine `#[from_row(flatten)]` with `#[from_row(rename = "..")]`, use `#[from_row(prefix = "..")]` to prepend a prefix to the columns of the flattened struct"#)
+                .into());
+        }
+
+        if self.flatten {
+            if let Some(attribute) = self.present_attributes().find(|attribute| {
+                matches!(
+                    attribute.kind,
+                    AttributeKind::ColumnConversion | AttributeKind::Missing
+                )
+            }) {
+                return Err(self
+                    .error(format!(
+                        "can't combine `#[from_row(flatten)]` with `{}`",
+                        attribute.name
+                    ))
+                    .into());
+            }
+        }
+
+        if self.rename_const.is_some() {
+            for (present, attribute) in [
+                (self.flatten, "#[from_row(flatten)]"),
+                (self.rename.is_some(), r#"#[from_row(rename = "..")]"#),
+            ] {
+                if present {
+                    return Err(self
+                        .error(format!(
+                            r#"can't combine `#[from_row(rename_const = "..")]` with `{attribute}`"#
+                        ))
+                        .into());
+                }
+            }
+        }
+
+        if self.column_index.is_some() {
+            for (present, attribute) in [
+                (self.flatten, "#[from_row(flatten)]"),
+                (self.rename.is_some(), r#"#[from_row(rename = "..")]"#),
+                (
+                    self.rename_const.is_some(),
+                    r#"#[from_row(rename_const = "..")]"#,
+                ),
+                (self.with.is_some(), r#"#[from_row(with = "..")]"#),
+            ] {
+                if present {
+                    return Err(self
+                        .error(format!(
+                            "can't combine `#[from_row(column = ..)]` with `{attribute}`"
+                        ))
+                        .into());
+                }
+            }
+        }
+
+        if self.array && !matches!(self.ty, syn::Type::Array(_)) {
+            return Err(Error::custom(
+                "`#[from_row(array)]` can only be used on fields of type `[T; N]`",
+            )
+            .with_span(&self.ty)
+            .into());
+        }
+
+        if self.empty_as_none && option_inner(&self.ty).is_none() {
+            return Err(Error::custom(
+                "`#[from_row(empty_as_none)]` can only be used on fields of type `Option<T>`",
+            )
+            .with_span(&self.ty)
+            .into());
+        }
+
+        if self.aggregate && generic_inner(&self.ty, "Vec").is_none() {
+            return Err(Error::custom(
+                "`#[from_row(aggregate)]` can only be used on fields of type `Vec<T>`",
+            )
+            .with_span(&self.ty)
+            .into());
+        }
+
+        if self.hstore && !is_hstore(&self.ty) {
+            return Err(Error::custom(
+                "`#[from_row(hstore)]` can only be used on fields of type `HashMap<String, Option<String>>`",
+            )
+            .with_span(&self.ty)
+            .into());
+        }
+
+        if self.nonzero_as_bool
+            && !matches!(&self.ty, syn::Type::Path(path) if path.path.is_ident("bool"))
+        {
+            return Err(Error::custom(
+                "`#[from_row(nonzero_as_bool)]` can only be used on fields of type `bool`",
+            )
+            .with_span(&self.ty)
+            .into());
+        }
+
+        if self.split.is_some() && self.split_element().is_none() {
+            return Err(Error::custom(
+                r#"`#[from_row(split = "..")]` can only be used on fields of type `Vec<T>` or `Option<Vec<T>>`"#,
+            )
+            .with_span(&self.ty)
+            .into());
+        }
+
+        if self.with.is_some()
+            && self.ident.is_none()
+            && self.rename.is_none()
+            && self.rename_const.is_none()
+        {
+            return Err(self.error(
+                r#"`#[from_row(with = "..")]` requires a column name, use `#[from_row(rename = "..")]` on fields of a tuple struct"#,
+            )
+            .into());
+        }
+
+        if let Some(wrap) = self.wrap {
+            if wrap.inner(&self.ty).is_none() {
+                return Err(Error::custom(format!(
+                    r#"`#[from_row(wrap = "{}")]` can only be used on fields of type `{}<T>`"#,
+                    wrap.name(),
+                    wrap.ty(),
+                ))
+                .with_span(&self.ty)
+                .into());
+            }
+        }
+
+        if self.rest {
+            if map_value(&self.ty).is_none() {
+                return Err(Error::custom(
+                    "`#[from_row(rest)]` can only be used on fields of type `HashMap<String, T>` or `BTreeMap<String, T>`",
+                )
+                .with_span(&self.ty)
+                .into());
+            }
+
+            self.reject_combinations("#[from_row(rest)]")?;
+        }
+
+        if self.get_or_default {
+            if let Some(attribute) = self.attributes().into_iter().find(|attribute| {
+                ![
+                    "#[from_row(get_or_default)]",
+                    r#"#[from_row(rename = "..")]"#,
+                    r#"#[from_row(rename_const = "..")]"#,
+                    "#[from_row(column = ..)]",
+                ]
+                .contains(attribute)
+            }) {
+                return Err(self
+                    .error(format!(
+                        "can't combine `#[from_row(get_or_default)]` with `{attribute}`"
+                    ))
+                    .into());
+            }
+        }
+
+        if self.children {
+            if generic_inner(&self.ty, "Vec").is_none() {
+                return Err(Error::custom(
+                    "`#[from_row(children)]` can only be used on fields of type `Vec<T>`",
+                )
+                .with_span(&self.ty)
+                .into());
+            }
+
+            if let Some(attribute) = self.attributes().into_iter().find(|attribute| {
+                ![
+                    "#[from_row(children)]",
+                    r#"#[from_row(prefix = "..")]"#,
+                    r#"#[from_row(null_check = "..")]"#,
+                ]
+                .contains(attribute)
+            }) {
+                return Err(self
+                    .error(format!(
+                        "can't combine `#[from_row(children)]` with `{attribute}`"
+                    ))
+                    .into());
+            }
+        }
+
+        if self.group_key {
+            for (present, attribute) in [
+                (self.flatten, "#[from_row(flatten)]"),
+                (self.skip, "#[from_row(skip)]"),
+            ] {
+                if present {
+                    return Err(self
+                        .error(format!(
+                            "can't combine `#[from_row(group_key)]` with `{attribute}`"
+                        ))
+                        .into());
+                }
+            }
+        }
+
+        if self.prefix.is_some() && !self.flatten && !self.children {
+            return Err(self
+                .error(r#"`#[from_row(prefix = "..")]` can only be used in combination with `#[from_row(flatten)]`"#)
+                .into());
+        }
+
+        if self.inherit_rename_all && !self.flatten {
+            return Err(self
+                .error("`#[from_row(inherit_rename_all)]` can only be used in combination with `#[from_row(flatten)]`")
+                .into());
+        }
+
+        if self.flatten
+            && self.null_check.is_none()
+            && option_inner(&self.ty).is_some_and(|inner| Wrapper::Box.inner(inner).is_some())
+            && self.conversion().is_none()
+        {
+            return Err(Error::custom(
+                r#"a flattened `Option<Box<T>>` requires `#[from_row(null_check = "..")]` naming the column that is `NULL` when it's `None`"#,
+            )
+            .with_span(&self.ty)
+            .into());
+        }
+
+        if self.null_check.is_some() && !self.children {
+            if !self.flatten {
+                return Err(self
+                    .error(r#"`#[from_row(null_check = "..")]` can only be used in combination with `#[from_row(flatten)]`"#)
+                    .into());
+            }
+
+            if let Some(conversion) = self.conversion() {
+                return Err(self
+                    .error(format!(
+                        r#"can't combine `#[from_row(null_check = "..")]` with `{conversion}`"#
+                    ))
+                    .into());
+            }
+
+            if option_inner(&self.ty).is_none() {
+                return Err(Error::custom(
+                    r#"`#[from_row(null_check = "..")]` can only be used on fields of type `Option<T>`"#,
+                )
+                .with_span(&self.ty)
+                .into());
+            }
+        }
+
+        if self.default_fn.is_some() && self.default {
+            return Err(self
+                .error(r#"can't combine `#[from_row(default)]` with `#[from_row(default_fn = "..")]`, `default_fn` already provides the value of a missing column"#)
+                .into());
+        }
+
+        if self.optional {
+            for (present, attribute) in [
+                (self.default, "#[from_row(default)]"),
+                (
+                    self.default_fn.is_some(),
+                    r#"#[from_row(default_fn = "..")]"#,
+                ),
+                (self.null_to_default, "#[from_row(null_to_default)]"),
+            ] {
+                if present {
+                    return Err(self
+                        .error(format!(
+                            "can't combine `#[from_row(optional)]` with `{attribute}`"
+                        ))
+                        .into());
+                }
+            }
+
+            if option_inner(&self.ty).is_none() {
+                return Err(Error::custom(
+                    "`#[from_row(optional)]` can only be used on fields of type `Option<T>`",
+                )
+                .with_span(&self.ty)
+                .into());
+            }
+        }
+
+        if self.skip {
+            self.reject_combinations("#[from_row(skip)]")?;
+        }
+
+        if self.from_row_fn.is_some() {
+            self.reject_combinations(r#"#[from_row(from_row_fn = "..")]"#)?;
+        }
+
+        if self.try_from_row_fn.is_some() {
+            self.reject_combinations(r#"#[from_row(try_from_row_fn = "..")]"#)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that this field only uses attributes that are supported by the `sqlx::FromRow` implementation.
+    fn validate_sqlx(&self) -> Result<()> {
+        let unsupported = self
+            .present_attributes()
+            .find(|attribute| !attribute.sqlx)
+            .map(|attribute| attribute.name)
+            .or_else(|| {
+                self.rename
+                    .as_ref()
+                    .is_some_and(|rename| rename.0.len() > 1)
+                    .then_some(r#"#[from_row(rename = [..])]"#)
+            });
+
+        if let Some(attribute) = unsupported {
+            return Err(self
+                .error(format!(
+                    "`{attribute}` is not supported in combination with `#[from_row(sqlx)]`"
+                ))
+                .into());
+        }
+
+        Ok(())
+    }
+
+    /// Wether this field is read from a single column of the row.
+    fn reads_column(&self) -> bool {
+        !(self.flatten
+            || self.children
+            || self.rest
+            || self.skip
+            || self.from_row_fn.is_some()
+            || self.try_from_row_fn.is_some())
+    }
+
+    /// Returns the feature of `postgres-from-row` that an attribute of this field requires, with that attribute.
+    fn required_feature(&self) -> Option<(&'static str, &'static str)> {
+        self.present_attributes()
+            .find_map(|attribute| attribute.feature.map(|feature| (feature, attribute.name)))
+    }
+
+    /// Returns all attributes that are set on this field.
+    fn attributes(&self) -> Vec<&'static str> {
+        self.present_attributes()
+            .map(|attribute| attribute.name)
+            .collect()
+    }
+
+    /// Returns an error if `attribute` is combined with any other attribute on this field.
+    fn reject_combinations(&self, attribute: &str) -> Result<()> {
+        match self
+            .attributes()
+            .into_iter()
+            .find(|other| *other != attribute)
+        {
+            Some(other) => Err(self
+                .error(format!("can't combine `{attribute}` with `{other}`"))
+                .into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Wether this field is read through one of the `__private` wrappers that hold the value of the field, or of the
+    /// `Option` around it, in their first field.
+    fn parsed_wrapper(&self) -> bool {
+        self.enum_text
+            || self.uuid_text
+            || self.decimal
+            || self.interval
+            || self.cast.is_some()
+            || self.split.is_some()
+    }
+
+    /// Returns a tokenstream of the type that should be returned from either
+    /// `FromRow` (when using `flatten`) or `FromSql`.
+    ///
+    /// When using `from_fn` or `try_from_fn` this is `_`, the type is then inferred from the function's argument.
+    fn target_ty(&self) -> Result<TokenStream2> {
+        if let Some(value) = self.rest.then(|| map_value(&self.ty)).flatten() {
+            Ok(value.to_token_stream())
+        } else if let Some(from) = &self.from {
+            Ok(from.to_token_stream())
+        } else if let Some(try_from) = &self.try_from {
+            Ok(try_from.to_token_stream())
+        } else if self.json {
+            let ty = &self.ty;
+            Ok(quote!(postgres_from_row::tokio_postgres::types::Json<#ty>))
+        } else if self.enum_text {
+            Ok(match option_inner(&self.ty) {
+                Some(inner) => {
+                    quote!(std::option::Option<postgres_from_row::__private::Parsed<#inner>>)
+                }
+                None => {
+                    let ty = &self.ty;
+                    quote!(postgres_from_row::__private::Parsed<#ty>)
+                }
+            })
+        } else if let Some(delimiter) = &self.split {
+            let element = self.split_element().unwrap_or(&self.ty);
+            let delimiter = delimiter.ident();
+            let split = quote!(postgres_from_row::__private::Split<#element, #delimiter>);
+
+            Ok(match option_inner(&self.ty) {
+                Some(_) => quote!(std::option::Option<#split>),
+                None => split,
+            })
+        } else if let Some(IntegerType(repr)) = &self.cast {
+            Ok(match option_inner(&self.ty) {
+                Some(inner) => {
+                    quote!(std::option::Option<postgres_from_row::__private::Cast<#inner, #repr>>)
+                }
+                None => {
+                    let ty = &self.ty;
+                    quote!(postgres_from_row::__private::Cast<#ty, #repr>)
+                }
+            })
+        } else if self.uuid_text {
+            Ok(match option_inner(&self.ty) {
+                Some(_) => quote!(std::option::Option<postgres_from_row::__private::UuidText>),
+                None => quote!(postgres_from_row::__private::UuidText),
+            })
+        } else if self.decimal {
+            Ok(match option_inner(&self.ty) {
+                Some(_) => quote!(std::option::Option<postgres_from_row::__private::Numeric>),
+                None => quote!(postgres_from_row::__private::Numeric),
+            })
+        } else if self.interval {
+            Ok(match option_inner(&self.ty) {
+                Some(_) => quote!(std::option::Option<postgres_from_row::__private::Interval>),
+                None => quote!(postgres_from_row::__private::Interval),
+            })
+        } else if self.json_text {
+            let ty = &self.ty;
+            Ok(quote!(postgres_from_row::__private::JsonText<#ty>))
+        } else if self.deserialize_with.is_some() {
+            Ok(quote!(std::string::String))
+        } else if self.wkb.is_some() {
+            Ok(quote!(postgres_from_row::__private::Wkb))
+        } else if self.utc {
+            Ok(quote!(postgres_from_row::__private::UtcDateTime))
+        } else if let Some(IntegerType(repr)) = &self.bit_flags {
+            Ok(repr.to_token_stream())
+        } else if self.nonzero_as_bool {
+            Ok(quote!(i64))
+        } else if self.null_to_default {
+            let ty = &self.ty;
+            Ok(quote!(std::option::Option<#ty>))
+        } else if let (true, syn::Type::Array(array)) = (self.array, &self.ty) {
+            let (elem, len) = (&array.elem, &array.len);
+            Ok(quote!(postgres_from_row::__private::FixedArray<#elem, { #len }>))
+        } else if let Some(inner) = self.wrapper().and_then(|wrap| wrap.inner(&self.ty)) {
+            Ok(match inner {
+                syn::Type::Path(path) if path.path.is_ident("str") => quote!(std::string::String),
+                syn::Type::Slice(slice) => {
+                    let elem = &slice.elem;
+                    quote!(std::vec::Vec<#elem>)
+                }
+                _ => inner.to_token_stream(),
+            })
+        } else if self.from_fn.is_some() || self.try_from_fn.is_some() {
+            Ok(quote!(_))
+        } else if let Some(inner) = self
+            .children
+            .then(|| generic_inner(&self.ty, "Vec"))
+            .flatten()
+        {
+            Ok(inner.to_token_stream())
+        } else if let Some(inner) = self.null_checked_box() {
+            Ok(inner.to_token_stream())
+        } else if let Some(inner) = self
+            .null_check
+            .as_ref()
+            .and_then(|_| option_inner(&self.ty))
+        {
+            Ok(inner.to_token_stream())
+        } else if let Some((lifetime, inner)) = self.borrowed_cow() {
+            Ok(quote!(&#lifetime #inner))
+        } else {
+            Ok(self.ty.to_token_stream())
+        }
+    }
+
+    /// Returns `T` for a `Vec<T>` or `Option<Vec<T>>` field, which is the element type of `split`.
+    fn split_element(&self) -> Option<&syn::Type> {
+        let ty = option_inner(&self.ty).unwrap_or(&self.ty);
+        generic_inner(ty, "Vec")
+    }
+
+    /// Returns `T` for a flattened `Option<Box<T>>` field with `null_check`, which reads `T` and boxes it when the
+    /// checked column isn't `NULL`.
+    fn null_checked_box(&self) -> Option<&syn::Type> {
+        self.null_check
+            .as_ref()
+            .and_then(|_| option_inner(&self.ty))
+            .and_then(|inner| Wrapper::Box.inner(inner))
+    }
+
+    /// Returns the smart pointer this field is wrapped in, which is the one given to `wrap` or `Box` for a flattened
+    /// `Box<T>` field without another conversion.
+    fn wrapper(&self) -> Option<Wrapper> {
+        let converted = self.from.is_some()
+            || self.try_from.is_some()
+            || self.from_fn.is_some()
+            || self.try_from_fn.is_some();
+
+        match self.wrap {
+            Some(wrap) => Some(wrap),
+            None if self.flatten && !converted => {
+                Wrapper::Box.inner(&self.ty).map(|_| Wrapper::Box)
+            }
+            None => None,
+        }
+    }
+
+    /// Returns the lifetime and the borrowed type of a field of type `Cow<'a, str>` or `Cow<'a, [u8]>`,
+    /// which is read as a reference bound to the row and wrapped in `Cow::Borrowed`.
+    ///
+    /// Returns `None` when the field is converted in any other way.
+    fn borrowed_cow(&self) -> Option<(&syn::Lifetime, &syn::Type)> {
+        let converted = self.flatten || self.children || self.conversion().is_some();
+
+        if converted {
+            return None;
+        }
+
+        cow_inner(&self.ty)
+    }
+
+    /// Returns the expression used for this field when its column is not present in the row,
+    /// or `None` if a missing column should be treated as an error.
+    ///
+    /// `default_fn` takes precedence over `Default::default()` from `default` or a `lenient` container, which takes
+    /// precedence over `None` from `optional`. `validate` rejects combining them on a single field, only `lenient`
+    /// is overridden by the attributes of a field. A `NULL` in a present column is handled by `null_to_default`.
+    fn default_value(&self, container: &DeriveFromRow) -> Result<Option<TokenStream2>> {
+        let field_ty = &self.ty;
+
+        if let Some(default_fn) = &self.default_fn {
+            Ok(Some(quote!(#default_fn())))
+        } else if self.defaults(container) {
+            Ok(Some(
+                quote!(<#field_ty as std::default::Default>::default()),
+            ))
+        } else if self.optional {
+            Ok(Some(quote!(std::option::Option::None)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Wether this field falls back to `Default::default()` when its column is absent, either because it has
+    /// `#[from_row(default)]` or because the container is `lenient` and the field has no other fallback.
+    fn defaults(&self, container: &DeriveFromRow) -> bool {
+        self.default
+            || (container.lenient
+                && self.reads_column()
+                && self.default_fn.is_none()
+                && !self.optional)
+    }
+
+    /// Returns the name that maps to the actuall sql column
+    /// By default this is the same as the rust field name but can be overwritten by `#[from_row(rename = "..")]`,
+    /// or by the container level `#[from_row(rename_all = "..")]`, `#[from_row(column_format = "..")]` and
+    /// `#[from_row(prefix = "..")]`, which are applied in that order.
+    /// The field of a `transparent` struct is always read from the column named by the struct's `rename`.
+    ///
+    /// Fields of a tuple struct have no name unless they are renamed, in which case this returns `None`.
+    fn column_name(&self, container: &DeriveFromRow) -> Option<String> {
+        if let (true, Some(rename)) = (container.transparent, &container.rename) {
+            return Some(rename.clone());
+        }
+
+        if let Some(rename) = &self.rename {
+            return Some(rename.0[0].clone());
+        }
+
+        let name = self.ident.as_ref()?.to_string();
+
+        let name = match container.rename_all {
+            Some(rule) => rule.apply(&name),
+            None => name,
+        };
+
+        let name = match &container.column_format {
+            Some(ColumnFormat(format)) => format.replacen("{}", &name, 1),
+            None => name,
+        };
+
+        Some(match &container.prefix {
+            Some(prefix) => format!("{prefix}{name}"),
+            None => name,
+        })
+    }
+
+    /// Returns the column this field is read from, `index` being the position of this field in the struct.
+    /// Unnamed fields of a tuple struct are read by their position,
+    /// and any field can be read by an explicit position with `#[from_row(column = ..)]`.
+    fn column(&self, container: &DeriveFromRow, index: usize) -> Column {
+        if let Some(column_index) = self.column_index {
+            return Column::Index(column_index);
+        }
+
+        if let Some(path) = &self.rename_const {
+            return Column::Const(path.clone());
+        }
+
+        if let Some(Renames(names)) = &self.rename {
+            if names.len() > 1 {
+                return Column::Candidates(names.clone());
+            }
+        }
+
+        match (self.column_name(container), &self.ident) {
+            (Some(name), Some(ident)) if container.inherit_rename_all && self.rename.is_none() => {
+                Column::Inherited {
+                    name,
+                    field: ident.to_string(),
+                }
+            }
+            (Some(name), _) => Column::Name(name),
+            (None, _) => Column::Index(index),
+        }
+    }
+
+    /// Wether reading this field depends on the naming convention that is passed to `from_row_with_rename_all`.
+    fn inherits_rename_all(&self, container: &DeriveFromRow) -> bool {
+        if container.rename_all.is_some() {
+            return false;
+        }
+
+        self.inherit_rename_all
+            || (self.reads_column()
+                && matches!(self.column(container, 0), Column::Inherited { .. }))
+    }
+
+    /// Returns the expression for the prefix passed to a flattened field, which is the prefix of the containing
+    /// struct followed by the prefix of this field.
+    fn flatten_prefix(&self) -> TokenStream2 {
+        match &self.prefix {
+            Some(own) => quote!(&*postgres_from_row::__private::prefixed(prefix, #own)),
+            None => quote!(prefix),
+        }
+    }
+
+    /// Returns the name of this field as it's used in error messages.
+    fn display_name(&self, index: usize) -> String {
+        match &self.ident {
+            Some(ident) => ident.to_string(),
+            None => index.to_string(),
+        }
+    }
+
+    /// Returns an expression for the name that is reported with the errors of this field by `try_from_row_verbose`.
+    ///
+    /// This is the prefixed column name for fields that read a single column, and the name of the field otherwise.
+    fn error_label(&self, container: &DeriveFromRow, index: usize) -> TokenStream2 {
+        if !self.reads_column() {
+            let name = self.display_name(index);
+            return quote!(std::string::String::from(#name));
+        }
+
+        match self.column(container, index) {
+            Column::Name(name) => {
+                quote!(postgres_from_row::__private::prefixed(prefix, #name).into_owned())
+            }
+            Column::Const(path) => {
+                quote!(postgres_from_row::__private::prefixed(prefix, #path).into_owned())
+            }
+            column @ (Column::Candidates(_) | Column::Inherited { .. }) => {
+                quote!(std::string::String::from(#column))
+            }
+            Column::Index(index) => {
+                quote!(std::string::ToString::to_string(&#index))
+            }
+        }
+    }
+
+    /// Returns the span of the ident of this field, or of its type when the field is unnamed.
+    fn span(&self) -> proc_macro2::Span {
+        match &self.ident {
+            Some(ident) => ident.span(),
+            None => syn::spanned::Spanned::span(&self.ty),
+        }
+    }
+
+    /// Creates an error that points at this field.
+    fn error(&self, message: impl std::fmt::Display) -> Error {
+        let error = Error::custom(message);
+
+        match &self.ident {
+            Some(ident) => error.with_span(ident),
+            None => error.with_span(&self.ty),
+        }
+    }
+
+    /// Returns the member used to initialize this field, `index` being the position of this field in the struct.
+    fn member(&self, index: usize) -> syn::Member {
+        match &self.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        }
+    }
+
+    /// Returns an expression that reads this flattened field with `prefix`, where `method` is either `from_row`
+    /// or `try_from_row`. Fields with `inherit_rename_all` also pass the naming convention of the container.
+    fn read_flattened(
+        &self,
+        container: &DeriveFromRow,
+        method: TokenStream2,
+        prefix: TokenStream2,
+    ) -> Result<TokenStream2> {
+        let target_ty = self.target_ty()?;
+        let from_row_trait = self.trait_path(container);
+
+        Ok(if self.inherit_rename_all {
+            let method = quote::format_ident!("{}_with_rename_all", method.to_string());
+            let rename_all = container.rename_all_expr();
+            quote!(<#target_ty as #from_row_trait>::#method(row, #prefix, #rename_all))
+        } else {
+            let method = quote::format_ident!("{}_with_prefix", method.to_string());
+            quote!(<#target_ty as #from_row_trait>::#method(row, #prefix))
+        })
+    }
+
+    /// Returns the trait a flattened field is read with, `FromBorrowedRow<'a>` when its type mentions the lifetime
+    /// the container borrows from the row with and `FromRow` otherwise.
+    fn trait_path(&self, container: &DeriveFromRow) -> TokenStream2 {
+        match container.borrowed() {
+            Some(lifetime) if container.own_lifetimes(&self.ty).contains(&lifetime) => {
+                quote!(postgres_from_row::FromBorrowedRow<#lifetime>)
+            }
+            _ => quote!(postgres_from_row::FromRow),
+        }
+    }
+
+    /// Pushes the needed where clause predicates for this field.
+    ///
+    /// By default this is `T: for<'__from_row_lifetime> postgres::types::FromSql<'__from_row_lifetime>`,
+    /// when using `flatten` it's: `T: postgres_from_row::FromRow`
+    /// and when using either `from` or `try_from` attributes it additionally pushes this bound:
+    /// `T: std::convert::From<R>`, where `T` is the type specified in the struct and `R` is the
+    /// type specified in the `[try]_from` attribute.
+    /// When using `default`, `null_to_default` or `get_or_default` it also pushes `T: std::default::Default`.
+    /// Skipped fields only require `T: std::default::Default`, and `from_row_fn` or `try_from_row_fn` require nothing.
+    /// When using `from_fn` or `try_from_fn` no bounds are pushed, since the source type is inferred from the function,
+    /// and neither are they for `with` since the module's functions read the column themselves.
+    /// A `try_from_fn` error is converted with the `?` operator, requiring `postgres_from_row::tokio_postgres::Error: From<E>`
+    /// just like `try_from` does. In `from_row` the error is included in the panic message, which requires `E: Debug`
+    /// through the bound of `__private::expect_converted` since `E` can't be named here.
+    ///
+    /// When the container borrows from the row, `FromSql` is bound to its lifetime instead: `T: FromSql<'a>`.
+    /// With `aggregate` the element type of the `Vec` must also be a composite type.
+    /// A `group_key` also requires `T: std::cmp::PartialEq`, and the element type of a `children` field is bound
+    /// like a flattened field.
+    /// With `deserialize_with` the column is read as a `String`, which needs no bound, and the error is converted like
+    /// the error of `try_from_fn`. `wkb` is handled the same, reading the `Wkb` wrapper instead of a `String`.
+    /// With `json_text` and `enum_text` the `JsonText<T>` and `Parsed<T>` wrappers are bound like any other target
+    /// type, their implementations of `FromSql` require `T: DeserializeOwned` and `T: FromStr`.
+    /// The `Split<T, D>` wrapper of `split` is bound like `Parsed<T>`, `D` is the type generated for the delimiter by
+    /// `with_crate`.
+    /// `uuid_text`, `decimal` and `interval` read the `UuidText`, `Numeric` and `Interval` wrappers, which aren't
+    /// generic and need no bound.
+    /// The `Cast<T, S>` wrapper of `cast` is bound like `Parsed<T>`, its implementation of `FromSql` requires
+    /// `T: TryFrom<S>`.
+    /// With `bit_flags` only the integer type is bound, the `from_bits_truncate` function of `T` isn't part of a
+    /// trait and is checked when the generated code calls it.
+    ///
+    /// Note: '__from_row_lifetime is used here to avoid conflicts with common user-specified lifetimes like 'a,
+    /// it can be overridden with `#[from_row(lifetime = "..")]`.
+    fn add_predicates(
+        &self,
+        container: &DeriveFromRow,
+        predicates: &mut Vec<TokenStream2>,
+    ) -> Result<()> {
+        let target_ty = &self.target_ty()?;
+        let ty = &self.ty;
+
+        if self.skip {
+            predicates.push(quote!(#ty: std::default::Default));
+            return Ok(());
+        }
+
+        if self.from_row_fn.is_some() || self.try_from_row_fn.is_some() {
+            return Ok(());
+        }
+
+        if self.from_fn.is_some()
+            || self.try_from_fn.is_some()
+            || self.deserialize_with.is_some()
+            || self.wkb.is_some()
+            || self.with.is_some()
+        {
+            if self.defaults(container) {
+                predicates.push(quote!(#ty: std::default::Default));
+            }
+
+            return Ok(());
+        }
+
+        predicates.push(if self.flatten || self.children {
+            let from_row_trait = self.trait_path(container);
+            quote! (#target_ty: #from_row_trait)
+        } else if let Some(lifetime) = container.borrowed() {
+            quote! (#target_ty: postgres_from_row::tokio_postgres::types::FromSql<#lifetime>)
+        } else {
+            let lifetime = container.generated_lifetime();
+            quote! (#target_ty: for<#lifetime> postgres_from_row::tokio_postgres::types::FromSql<#lifetime>)
+        });
+
+        if self.aggregate {
+            if let Some(element) = generic_inner(ty, "Vec") {
+                predicates.push(quote!(#element: postgres_from_row::__private::FromComposite));
+            }
+        }
+
+        if self.from.is_some() || self.wrapper().is_some() {
+            predicates.push(quote!(#ty: std::convert::From<#target_ty>))
+        } else if self.try_from.is_some() {
+            let try_from = quote!(std::convert::TryFrom<#target_ty>);
+
+            predicates.push(quote!(#ty: #try_from));
+            predicates.push(quote!(postgres_from_row::tokio_postgres::Error: std::convert::From<<#ty as #try_from>::Error>));
+            predicates.push(quote!(<#ty as #try_from>::Error: std::fmt::Debug));
+        }
+
+        if self.defaults(container) || self.null_to_default || self.get_or_default {
+            predicates.push(quote!(#ty: std::default::Default));
+        }
+
+        if self.rest {
+            predicates
+                .push(quote!(#ty: std::iter::FromIterator<(std::string::String, #target_ty)>));
+        }
+
+        if self.group_key {
+            predicates.push(quote!(#ty: std::cmp::PartialEq));
+        }
+
+        Ok(())
+    }
+
+    /// Pushes the where clause predicates this field needs in the `sqlx::FromRow` implementation.
+    fn add_sqlx_predicates(
+        &self,
+        container: &DeriveFromRow,
+        predicates: &mut Vec<TokenStream2>,
+    ) -> Result<()> {
+        let target_ty = &self.target_ty()?;
+        let ty = &self.ty;
+        let lifetime = container.generated_lifetime();
+
+        if self.skip || self.defaults(container) || self.null_to_default {
+            predicates.push(quote!(#ty: std::default::Default));
+        }
+
+        if self.skip || self.from_fn.is_some() {
+            return Ok(());
+        }
+
+        predicates.push(if self.flatten {
+            quote!(#target_ty: postgres_from_row::sqlx::FromRow<#lifetime, postgres_from_row::sqlx::postgres::PgRow>)
+        } else {
+            quote! {
+                #target_ty: postgres_from_row::sqlx::Decode<#lifetime, postgres_from_row::sqlx::Postgres>
+                    + postgres_from_row::sqlx::Type<postgres_from_row::sqlx::Postgres>
+            }
+        });
+
+        if self.from.is_some() || self.wrapper().is_some() {
+            predicates.push(quote!(#ty: std::convert::From<#target_ty>));
+        }
+
+        Ok(())
+    }
+
+    /// Generate the expression needed to retrieve this field from a postgres row in `sqlx::FromRow::from_row`.
+    fn generate_sqlx(&self, container: &DeriveFromRow, index: usize) -> Result<TokenStream2> {
+        if self.skip {
+            return Ok(quote!(std::default::Default::default()));
+        }
+
+        let column = self.column(container, index).sqlx_index();
+        let field_ty = &self.ty;
+        let target_ty = self.target_ty()?;
+
+        let mut base = if self.flatten {
+            let lifetime = container.generated_lifetime();
+            quote!(<#target_ty as postgres_from_row::sqlx::FromRow<#lifetime, postgres_from_row::sqlx::postgres::PgRow>>::from_row(row)?)
+        } else {
+            quote!(postgres_from_row::sqlx::Row::try_get::<#target_ty, _>(row, #column)?)
+        };
+
+        if self.from.is_some() || self.wrapper().is_some() {
+            base = quote!(<#field_ty as std::convert::From<#target_ty>>::from(#base));
+        } else if let Some(from_fn) = &self.from_fn {
+            base = quote!(#from_fn(#base));
+        } else if self.bit_flags.is_some() {
+            base = quote!(<#field_ty>::from_bits_truncate(#base));
+        } else if self.nonzero_as_bool {
+            base = quote!(#base != 0);
+        } else if self.empty_as_none {
+            base = quote!(std::option::Option::filter(#base, |value| !value.is_empty()));
+        } else if self.null_to_default {
+            base = quote!(std::option::Option::unwrap_or_default(#base));
+        } else if self.borrowed_cow().is_some() {
+            base = quote!(std::borrow::Cow::Borrowed(#base));
+        }
+
+        if let Some(default_value) = self.default_value(container)? {
+            base = quote! {
+                if postgres_from_row::sqlx::Row::try_column(row, #column).is_ok() {
+                    #base
+                } else {
+                    #default_value
+                }
+            };
+        }
+
+        Ok(base)
+    }
+
+    /// Generate the expression for a flattened field with `null_check`, which is `None` when the checked column
+    /// is `NULL` and otherwise reads the flattened struct.
+    ///
+    /// With an `owner` this generates the expression for `from_row`, which panics with a message naming the field,
+    /// otherwise it generates the expression for `try_from_row`.
+    fn generate_null_checked(
+        &self,
+        container: &DeriveFromRow,
+        owner: Option<&str>,
+    ) -> Result<TokenStream2> {
+        let null_check = Column::Name(self.null_check.clone().unwrap_or_default());
+        let lookup = Lookup {
+            case_insensitive: container.case_insensitive,
+            slot: None,
+        };
+        let check_ty = quote!(std::option::Option<postgres_from_row::__private::Ignored>);
+
+        let prefix = match &self.prefix {
+            Some(own) => {
+                quote!(let prefix: &str = &postgres_from_row::__private::prefixed(prefix, #own);)
+            }
+            None => quote!(),
+        };
+
+        let (check, inner) = match owner {
+            Some(owner) => (
+                null_check.get(&check_ty, lookup, owner),
+                self.read_flattened(container, quote!(from_row), quote!(prefix))?,
+            ),
+            None => {
+                let check = null_check.read(quote!(try_get), &check_ty, lookup);
+                (quote!(#check?), {
+                    let read =
+                        self.read_flattened(container, quote!(try_from_row), quote!(prefix))?;
+                    quote!(#read?)
+                })
+            }
+        };
+
+        let inner = match self.null_checked_box() {
+            Some(_) => quote!(std::boxed::Box::new(#inner)),
+            None => inner,
+        };
+
+        Ok(quote! {
+            {
+                #prefix
+
+                if #check.is_some() {
+                    Some(#inner)
+                } else {
+                    None
+                }
+            }
+        })
+    }
+
+    /// Generates the `Option<T>` of a `children` field that is read from a single row, which is `None` when the
+    /// `null_check` column is `NULL`.
+    ///
+    /// With an `owner` this generates the expression for `from_grouped_rows`, otherwise the expression for
+    /// `try_from_grouped_rows`.
+    fn generate_child(
+        &self,
+        container: &DeriveFromRow,
+        owner: Option<&str>,
+    ) -> Result<TokenStream2> {
+        if self.null_check.is_some() {
+            return self.generate_null_checked(container, owner);
+        }
+
+        let prefix = self.flatten_prefix();
+
+        Ok(match owner {
+            Some(_) => {
+                let read = self.read_flattened(container, quote!(from_row), prefix)?;
+                quote!(std::option::Option::Some(#read))
+            }
+            None => {
+                let read = self.read_flattened(container, quote!(try_from_row), prefix)?;
+                quote!(std::option::Option::Some(#read?))
+            }
+        })
+    }
+
+    /// Generate the expression needed to retrieve this field from a row when calling `from_row`.
+    ///
+    /// When `slot` is set, the column is read at the position resolved in `indices` instead of by name.
+    /// Panics name the field as `owner::field`.
+    fn generate_from_row(
+        &self,
+        container: &DeriveFromRow,
+        index: usize,
+        slot: Option<usize>,
+        owner: &str,
+    ) -> Result<TokenStream2> {
+        if self.skip {
+            return Ok(quote!(std::default::Default::default()));
+        }
+
+        if let Some(from_row_fn) = &self.from_row_fn {
+            return Ok(quote!(#from_row_fn(row)));
+        }
+
+        if let Some(try_from_row_fn) = &self.try_from_row_fn {
+            let field = format!("field {owner}::{}", self.display_name(index));
+            return Ok(
+                quote!(postgres_from_row::__private::expect_converted(#try_from_row_fn(row), #field)),
+            );
+        }
+
+        if self.children {
+            return Ok(quote!(std::iter::FromIterator::from_iter(child)));
+        }
+
+        if self.rest {
+            let field = format!("field {owner}::{}", self.display_name(index));
+            let candidates = container.rest_candidates();
+            let case_insensitive = container.case_insensitive;
+            return Ok(quote! {
+                match postgres_from_row::__private::rest_columns(row, prefix, Self::COLUMNS, #candidates, #case_insensitive) {
+                    Ok(value) => value,
+                    Err(error) => panic!("failed to get the remaining columns for {}: {}", #field, error),
+                }
+            });
+        }
+
+        if self.null_check.is_some() {
+            let field = format!("field {owner}::{}", self.display_name(index));
+            return self.generate_null_checked(container, Some(&field));
+        }
+
+        let column = self.column(container, index);
+        let lookup = Lookup {
+            case_insensitive: container.case_insensitive,
+            slot,
+        };
+        let field_ty = &self.ty;
+        let target_ty = self.target_ty()?;
+
+        let mut base = if self.flatten {
+            let prefix = self.flatten_prefix();
+            self.read_flattened(container, quote!(from_row), prefix)?
+        } else if let Some(with) = &self.with {
+            quote!(#with::from_row_value(row, #column))
+        } else if self.get_or_default {
+            let read = column.read(quote!(try_get), &target_ty, lookup);
+            quote!(std::result::Result::unwrap_or_default(#read))
+        } else {
+            let field = format!("field {owner}::{}", self.display_name(index));
+            column.get(&target_ty, lookup, &field)
+        };
+
+        if self.from.is_some() || self.wrapper().is_some() {
+            base = quote!(<#field_ty as std::convert::From<#target_ty>>::from(#base));
+        } else if self.try_from.is_some() {
+            let field = format!("field {owner}::{}", self.display_name(index));
+            base = quote!(postgres_from_row::__private::expect_converted(<#field_ty as std::convert::TryFrom<#target_ty>>::try_from(#base), #field));
+        } else if let Some(from_fn) = &self.from_fn {
+            base = quote!(#from_fn(#base));
+        } else if let Some(try_from_fn) = &self.try_from_fn {
+            let field = format!("field {owner}::{}", self.display_name(index));
+            base =
+                quote!(postgres_from_row::__private::expect_converted(#try_from_fn(#base), #field));
+        } else if let Some(deserialize_with) = &self.deserialize_with {
+            let field = format!("field {owner}::{}", self.display_name(index));
+            base = quote!(postgres_from_row::__private::expect_converted(#deserialize_with(&#base), #field));
+        } else if let Some(wkb) = &self.wkb {
+            let field = format!("field {owner}::{}", self.display_name(index));
+            base = quote!(postgres_from_row::__private::expect_converted(#wkb(&#base.0), #field));
+        } else if self.json || self.json_text || self.utc || self.array {
+            base = quote!(#base.0);
+        } else if self.parsed_wrapper() && option_inner(field_ty).is_some() {
+            base = quote!(std::option::Option::map(#base, |parsed| parsed.0));
+        } else if self.parsed_wrapper() {
+            base = quote!(#base.0);
+        } else if self.bit_flags.is_some() {
+            base = quote!(<#field_ty>::from_bits_truncate(#base));
+        } else if self.nonzero_as_bool {
+            base = quote!(#base != 0);
+        } else if self.empty_as_none {
+            base = quote!(std::option::Option::filter(#base, |value| !value.is_empty()));
+        } else if self.null_to_default {
+            base = quote!(std::option::Option::unwrap_or_default(#base));
+        } else if self.borrowed_cow().is_some() {
+            base = quote!(std::borrow::Cow::Borrowed(#base));
+        };
+
+        if let Some(default_value) = self.default_value(container)? {
+            let is_present = column.is_present(lookup);
+            base = quote!(if #is_present { #base } else { #default_value });
+        }
+
+        Ok(base)
+    }
+
+    /// Generate the expression needed to retrieve this field from a row when calling `try_from_row`,
+    /// which returns early on errors.
+    ///
+    /// When `slot` is set, the column is read at the position resolved in `indices` instead of by name.
+    /// For flattened fields `flattened` can hold the already converted nested struct, which is then only
+    /// converted into the type of the field.
+    fn generate_try_from_row(
+        &self,
+        container: &DeriveFromRow,
+        index: usize,
+        slot: Option<usize>,
+        flattened: Option<TokenStream2>,
+    ) -> Result<TokenStream2> {
+        if self.skip {
+            return Ok(quote!(std::default::Default::default()));
+        }
+
+        if let Some(from_row_fn) = &self.from_row_fn {
+            return Ok(quote!(#from_row_fn(row)));
+        }
+
+        if let Some(try_from_row_fn) = &self.try_from_row_fn {
+            return Ok(quote!(#try_from_row_fn(row)?));
+        }
+
+        if self.children {
+            return Ok(quote!(std::iter::FromIterator::from_iter(child)));
+        }
+
+        if self.rest {
+            let candidates = container.rest_candidates();
+            let case_insensitive = container.case_insensitive;
+
+            return Ok(quote!(postgres_from_row::__private::rest_columns(
+                row,
+                prefix,
+                Self::COLUMNS,
+                #candidates,
+                #case_insensitive
+            )?));
+        }
+
+        if self.null_check.is_some() {
+            return self.generate_null_checked(container, None);
+        }
+
+        let column = self.column(container, index);
+        let lookup = Lookup {
+            case_insensitive: container.case_insensitive,
+            slot,
+        };
+        let field_ty = &self.ty;
+        let target_ty = self.target_ty()?;
+
+        let mut base = if let Some(flattened) = flattened {
+            flattened
+        } else if self.flatten {
+            let prefix = self.flatten_prefix();
+            let read = self.read_flattened(container, quote!(try_from_row), prefix)?;
+            quote!(#read?)
+        } else if let Some(with) = &self.with {
+            quote!(#with::try_from_row_value(row, #column)?)
+        } else {
+            let read = column.read(quote!(try_get), &target_ty, lookup);
+            quote!(#read?)
+        };
+
+        if self.from.is_some() || self.wrapper().is_some() {
+            base = quote!(<#field_ty as std::convert::From<#target_ty>>::from(#base));
+        } else if self.try_from.is_some() {
+            base = quote!(<#field_ty as std::convert::TryFrom<#target_ty>>::try_from(#base)?);
+        } else if let Some(from_fn) = &self.from_fn {
+            base = quote!(#from_fn(#base));
+        } else if let Some(try_from_fn) = &self.try_from_fn {
+            base = quote!(#try_from_fn(#base)?);
+        } else if let Some(deserialize_with) = &self.deserialize_with {
+            base = quote!(#deserialize_with(&#base)?);
+        } else if let Some(wkb) = &self.wkb {
+            base = quote!(#wkb(&#base.0)?);
+        } else if self.json || self.json_text || self.utc || self.array {
+            base = quote!(#base.0);
+        } else if self.parsed_wrapper() && option_inner(field_ty).is_some() {
+            base = quote!(std::option::Option::map(#base, |parsed| parsed.0));
+        } else if self.parsed_wrapper() {
+            base = quote!(#base.0);
+        } else if self.bit_flags.is_some() {
+            base = quote!(<#field_ty>::from_bits_truncate(#base));
+        } else if self.nonzero_as_bool {
+            base = quote!(#base != 0);
+        } else if self.empty_as_none {
+            base = quote!(std::option::Option::filter(#base, |value| !value.is_empty()));
+        } else if self.null_to_default {
+            base = quote!(std::option::Option::unwrap_or_default(#base));
+        } else if self.borrowed_cow().is_some() {
+            base = quote!(std::borrow::Cow::Borrowed(#base));
+        };
+
+        if let Some(default_value) = self.default_value(container)? {
+            let is_present = column.is_present(lookup);
+            base = quote!(if #is_present { #base } else { #default_value });
+        }
+
+        Ok(base)
+    }
+}
+
+/// Returns the where clause predicates of an implementation, which are the predicates written on the type followed by
+/// the `generated` ones that aren't already present.
+///
+/// Predicates are compared by their tokens, so fields of the same type or a bound the user already wrote are only
+/// included once.
+fn merge_predicates(
+    where_clause: Option<&syn::WhereClause>,
+    generated: Vec<TokenStream2>,
+) -> Vec<TokenStream2> {
+    let mut predicates: Vec<TokenStream2> = where_clause
+        .into_iter()
+        .flat_map(|where_clause| &where_clause.predicates)
+        .map(ToTokens::to_token_stream)
+        .collect();
+
+    let mut seen: Vec<String> = predicates.iter().map(ToString::to_string).collect();
+
+    for predicate in generated {
+        let key = predicate.to_string();
+
+        if !seen.contains(&key) {
+            seen.push(key);
+            predicates.push(predicate);
+        }
+    }
+
+    predicates
+}
+
+/// Returns a constant expression for a list of column names, being `names` followed by the `nested` lists.
+///
+/// Each nested list is a tuple of a prefix and the columns of a flattened type, which are concatenated at compile time.
+fn columns_const(names: &[TokenStream2], nested: &[TokenStream2]) -> TokenStream2 {
+    if nested.is_empty() {
+        return quote!(&[#(#names),*]);
+    }
+
+    quote! {
+        {
+            const PARTS: &[(&str, &[&str])] = &[("", &[#(#names),*]), #(#nested),*];
+            const BYTES: [u8; postgres_from_row::__private::columns_byte_len(PARTS)] =
+                postgres_from_row::__private::columns_bytes(PARTS);
+            const COLUMNS: [&str; postgres_from_row::__private::columns_count(PARTS)] =
+                postgres_from_row::__private::columns_split(&BYTES, PARTS);
+
+            &COLUMNS
+        }
+    }
+}
+
+/// Collects the names of all lifetimes in `tokens`, without their leading apostrophe.
+fn mentioned_lifetimes(tokens: TokenStream2, lifetimes: &mut Vec<String>) {
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            proc_macro2::TokenTree::Group(group) => mentioned_lifetimes(group.stream(), lifetimes),
+            proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                if let Some(proc_macro2::TokenTree::Ident(ident)) = tokens.peek() {
+                    lifetimes.push(ident.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns `T` if `ty` is written as `Option<T>`.
+///
+/// This is a syntactic check, so a type alias of `Option` is not recognized.
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    generic_inner(ty, "Option")
+}
+
+/// Returns `T` if `ty` is written as `HashMap<String, T>` or `BTreeMap<String, T>`, optionally with a hasher.
+///
+/// This is a syntactic check like `option_inner`.
+fn map_value(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+
+    let segment = path.path.segments.last()?;
+
+    if segment.ident != "HashMap" && segment.ident != "BTreeMap" {
+        return None;
+    }
+
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+
+    let mut arguments = arguments.args.iter();
+
+    match (arguments.next()?, arguments.next()?) {
+        (syn::GenericArgument::Type(syn::Type::Path(key)), syn::GenericArgument::Type(value))
+            if key.path.is_ident("String") =>
+        {
+            Some(value)
+        }
+        _ => None,
+    }
+}
+
+/// Checks wether `ty` is written as `HashMap<String, Option<String>>`, optionally with a hasher, which is the type
+/// that `tokio_postgres` reads a `hstore` column into.
+fn is_hstore(ty: &syn::Type) -> bool {
+    let syn::Type::Path(path) = ty else {
+        return false;
+    };
+
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return false;
+    };
+
+    let mut arguments = arguments.args.iter();
+    let is_string = |ty: &syn::Type| matches!(ty, syn::Type::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "String"));
+
+    segment.ident == "HashMap"
+        && matches!(arguments.next(), Some(syn::GenericArgument::Type(key)) if is_string(key))
+        && matches!(arguments.next(), Some(syn::GenericArgument::Type(value)) if option_inner(value).is_some_and(is_string))
+}
+
+/// Returns `T` if `ty` is written as `Name<T>`, where `name` is the last segment of the path.
+fn generic_inner<'a>(ty: &'a syn::Type, name: &str) -> Option<&'a syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+
+    let segment = path.path.segments.last()?;
+
+    if segment.ident != name {
+        return None;
+    }
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+            match arguments.args.first()? {
+                syn::GenericArgument::Type(inner) => Some(inner),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns `'a` and `T` if `ty` is written as `Cow<'a, str>` or `Cow<'a, [u8]>`.
+///
+/// This is a syntactic check like `option_inner`.
+fn cow_inner(ty: &syn::Type) -> Option<(&syn::Lifetime, &syn::Type)> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+
+    let segment = path.path.segments.last()?;
+
+    if segment.ident != "Cow" {
+        return None;
+    }
+
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+
+    let mut arguments = arguments.args.iter();
+
+    let (
+        Some(syn::GenericArgument::Lifetime(lifetime)),
+        Some(syn::GenericArgument::Type(inner)),
+        None,
+    ) = (arguments.next(), arguments.next(), arguments.next())
+    else {
+        return None;
+    };
+
+    let supported = match inner {
+        syn::Type::Path(path) => path.path.is_ident("str"),
+        syn::Type::Slice(slice) => {
+            matches!(&*slice.elem, syn::Type::Path(elem) if elem.path.is_ident("u8"))
+        }
+        _ => false,
+    };
+
+    supported.then_some((lifetime, inner))
+}
+
+/// Parses the type given to `from` or `try_from`, reporting a type that doesn't parse at the attribute.
+fn parse_type(meta: &syn::Meta) -> darling::Result<Option<syn::Type>> {
+    parse_string(meta, "a type")
+}
+
+/// Parses the function given to `from_fn` or `try_from_fn`, reporting a path that doesn't parse at the attribute.
+fn parse_function(meta: &syn::Meta) -> darling::Result<Option<syn::ExprPath>> {
+    parse_string(meta, "a path to a function")
+}
+
+/// Parses the string value of `meta` as `T`, including the parse error in the message when it fails.
+fn parse_string<T: syn::parse::Parse>(
+    meta: &syn::Meta,
+    expected: &str,
+) -> darling::Result<Option<T>> {
+    let value = String::from_meta(meta)?;
+
+    syn::parse_str(&value).map(Some).map_err(|error| {
+        let name = meta.path().to_token_stream();
+
+        Error::custom(format!(
+            "`{name}` expects {expected}, but `{value}` could not be parsed: {error}"
+        ))
+        .with_span(meta)
+    })
+}
+
+/// The column names given to `#[from_row(rename = "..")]`, either a single name or an array of candidates.
+/// An empty name is rejected, since it would only fail at runtime with a column that can't be found.
+#[derive(Debug, Clone)]
+struct Renames(Vec<String>);
+
+impl FromMeta for Renames {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        if value.is_empty() {
+            return Err(Error::custom(
+                "expected a column name, remove `rename` to read the column with the name of the field",
+            ));
+        }
+
+        Ok(Renames(vec![value.to_owned()]))
+    }
+
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        match expr {
+            syn::Expr::Array(array) if array.elems.is_empty() => {
+                Err(Error::custom("expected at least one column name").with_span(array))
+            }
+            syn::Expr::Array(array) => array
+                .elems
+                .iter()
+                .map(|elem| match String::from_expr(elem)? {
+                    name if name.is_empty() => {
+                        Err(Error::custom("expected a column name").with_span(elem))
+                    }
+                    name => Ok(name),
+                })
+                .collect::<darling::Result<_>>()
+                .map(Renames),
+            syn::Expr::Lit(lit) => Self::from_value(&lit.lit),
+            _ => Err(Error::unexpected_expr_type(expr)),
+        }
+    }
+}
+
+/// The delimiter given to `#[from_row(split = "..")]`, which can't be empty.
+#[derive(Debug, Clone)]
+struct Delimiter(String);
+
+impl Delimiter {
+    /// Returns the name of the type that is generated for this delimiter, which is passed to the `Split` wrapper.
+    ///
+    /// The name is derived from the bytes of the delimiter, so fields with the same delimiter share the type.
+    fn ident(&self) -> syn::Ident {
+        let hex: String = self.0.bytes().map(|byte| format!("{byte:02X}")).collect();
+        quote::format_ident!("__FromRowDelimiter{hex}")
+    }
+
+    /// Generate the type that implements `Delimiter` for this delimiter.
+    fn definition(&self) -> TokenStream2 {
+        let ident = self.ident();
+        let delimiter = &self.0;
+
+        quote! {
+            struct #ident;
+
+            impl postgres_from_row::__private::Delimiter for #ident {
+                const DELIMITER: &'static str = #delimiter;
+            }
+        }
+    }
+}
+
+impl FromMeta for Delimiter {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        if value.is_empty() {
+            return Err(Error::custom("expected a delimiter such as `,`"));
+        }
+
+        Ok(Delimiter(value.to_owned()))
+    }
+}
+
+/// The lifetime given to `#[from_row(lifetime = "..")]`.
+#[derive(Debug, Clone)]
+struct LifetimeName(syn::Lifetime);
+
+impl FromMeta for LifetimeName {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        syn::parse_str(value).map(LifetimeName).map_err(|_| {
+            Error::custom(format!(
+                "expected a lifetime such as `'row`, found `{value}`"
+            ))
+        })
+    }
+}
+
+/// The format given to `#[from_row(column_format = "..")]`, which contains exactly one `{}`.
+#[derive(Debug, Clone)]
+struct ColumnFormat(String);
+
+impl FromMeta for ColumnFormat {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        if value.matches("{}").count() != 1 {
+            return Err(Error::custom(format!(
+                "expected a format with exactly one `{{}}` such as `todo_{{}}`, found `{value}`"
+            )));
+        }
+
+        Ok(ColumnFormat(value.to_owned()))
+    }
+}
+
+/// The integer type given to `#[from_row(bit_flags = "..")]` or `#[from_row(cast = "..")]`, which is `i64` when no
+/// type is given.
+#[derive(Debug, Clone)]
+struct IntegerType(syn::Type);
+
+impl FromMeta for IntegerType {
+    fn from_word() -> darling::Result<Self> {
+        Ok(IntegerType(syn::parse_quote!(i64)))
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        syn::parse_str(value).map(IntegerType).map_err(|_| {
+            Error::custom(format!(
+                "expected an integer type such as `i32`, found `{value}`"
+            ))
+        })
+    }
+}
+
+/// A smart pointer that is supported by `#[from_row(wrap = "..")]`.
+#[derive(Debug, Clone, Copy)]
+enum Wrapper {
+    /// `std::boxed::Box`
+    Box,
+    /// `std::rc::Rc`
+    Rc,
+    /// `std::sync::Arc`
+    Arc,
+}
+
+impl Wrapper {
+    /// All supported wrappers paired with the name used to select them.
+    const ALL: &'static [(&'static str, Wrapper)] = &[
+        ("box", Wrapper::Box),
+        ("rc", Wrapper::Rc),
+        ("arc", Wrapper::Arc),
+    ];
+
+    /// Returns the name used in `#[from_row(wrap = "..")]`.
+    fn name(self) -> &'static str {
+        match self {
+            Wrapper::Box => "box",
+            Wrapper::Rc => "rc",
+            Wrapper::Arc => "arc",
+        }
+    }
+
+    /// Returns the name of the smart pointer type.
+    fn ty(self) -> &'static str {
+        match self {
+            Wrapper::Box => "Box",
+            Wrapper::Rc => "Rc",
+            Wrapper::Arc => "Arc",
+        }
+    }
+
+    /// Returns `T` if `ty` is written as this smart pointer around `T`.
+    fn inner(self, ty: &syn::Type) -> Option<&syn::Type> {
+        generic_inner(ty, self.ty())
+    }
+}
+
+impl FromMeta for Wrapper {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Wrapper::ALL
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, wrapper)| *wrapper)
+            .ok_or_else(|| {
+                let expected = Wrapper::ALL
+                    .iter()
+                    .map(|(name, _)| format!("`{name}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                Error::custom(format!(
+                    "unknown wrapper `{value}`, expected one of {expected}"
+                ))
+            })
+    }
+}
+
+/// The column a field is read from, either by name or by position.
+///
+/// When converted to tokens a name is prefixed with the `prefix` that is passed to `from_row_with_prefix`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Column {
+    /// Read the column with this name.
+    Name(String),
+    /// Read the column named by this `&str` constant.
+    Const(syn::Path),
+    /// Read the first column of these names that is present in the row.
+    Candidates(Vec<String>),
+    /// Read the column with this name, or the column named after `field` with the naming convention that is
+    /// passed to `from_row_with_rename_all`.
+    Inherited { name: String, field: String },
+    /// Read the column at this position.
+    Index(usize),
+}
+
+impl Column {
+    /// Returns the type used to index a row for this column.
+    fn index_ty(&self) -> TokenStream2 {
+        match self {
+            Column::Name(_)
+            | Column::Const(_)
+            | Column::Candidates(_)
+            | Column::Inherited { .. } => {
+                quote!(&str)
+            }
+            Column::Index(_) => quote!(usize),
+        }
+    }
+
+    /// Returns the name of this column, which is the first name for candidates and `None` for a position.
+    fn name(&self) -> Option<&str> {
+        match self {
+            Column::Name(name) | Column::Inherited { name, .. } => Some(name),
+            Column::Candidates(names) => names.first().map(String::as_str),
+            Column::Const(_) | Column::Index(_) => None,
+        }
+    }
+
+    /// Returns an expression for the name of this column, which unlike `name` includes the constant of
+    /// `rename_const`.
+    fn name_tokens(&self) -> Option<TokenStream2> {
+        match self {
+            Column::Const(path) => Some(quote!(#path)),
+            _ => self.name().map(|name| quote!(#name)),
+        }
+    }
+
+    /// Returns an expression that reads this column from `row`, where `method` is either `get` or `try_get`.
+    fn read(&self, method: TokenStream2, target_ty: &TokenStream2, lookup: Lookup) -> TokenStream2 {
+        let index_ty = self.index_ty();
+        let read = quote!(postgres_from_row::tokio_postgres::Row::#method::<#index_ty, #target_ty>(row, #self));
+
+        let by_index = |index: TokenStream2, fallback: TokenStream2| {
+            quote! {
+                match #index {
+                    Some(index) => postgres_from_row::tokio_postgres::Row::#method::<usize, #target_ty>(row, index),
+                    None => #fallback,
+                }
+            }
+        };
+
+        let read = match self {
+            Column::Name(_)
+            | Column::Const(_)
+            | Column::Candidates(_)
+            | Column::Inherited { .. }
+                if lookup.case_insensitive =>
+            {
+                by_index(
+                    quote!(postgres_from_row::__private::find_column(postgres_from_row::tokio_postgres::Row::columns(row), #self)),
+                    read,
+                )
+            }
+            _ => read,
+        };
+
+        match lookup.slot {
+            Some(slot) => by_index(
+                quote!(postgres_from_row::ColumnIndices::get(indices, #slot)),
+                read,
+            ),
+            None => read,
+        }
+    }
+
+    /// Returns an expression that reads this column from `row`, panicking with a message that names both the
+    /// column and `owner` when the column can't be read.
+    fn get(&self, target_ty: &TokenStream2, lookup: Lookup, owner: &str) -> TokenStream2 {
+        let read = self.read(quote!(try_get), target_ty, lookup);
+
+        let panic = match self {
+            Column::Name(name) => quote! {
+                panic!("failed to get column {:?} for {}: {}", postgres_from_row::__private::prefixed(prefix, #name), #owner, error)
+            },
+            Column::Const(path) => quote! {
+                panic!("failed to get column {:?} for {}: {}", postgres_from_row::__private::prefixed(prefix, #path), #owner, error)
+            },
+            Column::Candidates(_) | Column::Inherited { .. } => quote! {
+                panic!("failed to get column {:?} for {}: {}", #self, #owner, error)
+            },
+            Column::Index(index) => quote! {
+                panic!("failed to get column at index {} for {}: {}", #index, #owner, error)
+            },
         };
 
-        Ok(quote!(#ident: #base))
+        quote! {
+            match #read {
+                Ok(value) => value,
+                Err(error) => #panic,
+            }
+        }
+    }
+
+    /// Returns the index used to read this column from an `sqlx` row, which doesn't support prefixes.
+    fn sqlx_index(&self) -> TokenStream2 {
+        match self {
+            Column::Name(name) | Column::Inherited { name, .. } => quote!(#name),
+            Column::Const(path) => quote!(#path),
+            Column::Candidates(names) => {
+                let name = &names[0];
+                quote!(#name)
+            }
+            Column::Index(index) => quote!(#index),
+        }
+    }
+
+    /// Returns an expression that checks wether this column is present in `row`.
+    fn is_present(&self, lookup: Lookup) -> TokenStream2 {
+        if let Some(slot) = lookup.slot {
+            return quote!(postgres_from_row::ColumnIndices::get(indices, #slot).is_some());
+        }
+
+        match self {
+            Column::Name(_)
+            | Column::Const(_)
+            | Column::Candidates(_)
+            | Column::Inherited { .. }
+                if lookup.case_insensitive =>
+            {
+                quote!(postgres_from_row::__private::find_column(postgres_from_row::tokio_postgres::Row::columns(row), #self).is_some())
+            }
+            Column::Name(_)
+            | Column::Const(_)
+            | Column::Candidates(_)
+            | Column::Inherited { .. } => {
+                quote!(postgres_from_row::__private::has_column(row, #self))
+            }
+            Column::Index(index) => {
+                quote!(#index < postgres_from_row::tokio_postgres::Row::len(row))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Column {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Column::Name(name) | Column::Inherited { name, .. } => write!(f, "`{name}`"),
+            Column::Const(path) => write!(f, "`{}`", path.to_token_stream()),
+            Column::Candidates(names) => {
+                let names = names
+                    .iter()
+                    .map(|name| format!("`{name}`"))
+                    .collect::<Vec<_>>();
+
+                write!(f, "{}", names.join(" or "))
+            }
+            Column::Index(index) => write!(f, "at index {index}"),
+        }
+    }
+}
+
+impl ToTokens for Column {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Column::Name(name) => {
+                quote!(&*postgres_from_row::__private::prefixed(prefix, #name)).to_tokens(tokens)
+            }
+            Column::Const(path) => {
+                quote!(&*postgres_from_row::__private::prefixed(prefix, #path)).to_tokens(tokens)
+            }
+            Column::Candidates(names) => {
+                quote!(&*postgres_from_row::__private::first_present(row, prefix, &[#(#names),*]))
+                    .to_tokens(tokens)
+            }
+            Column::Inherited { name, field } => {
+                quote!(&*postgres_from_row::__private::renamed(prefix, rename_all, #name, #field))
+                    .to_tokens(tokens)
+            }
+            Column::Index(index) => {
+                proc_macro2::Literal::usize_unsuffixed(*index).to_tokens(tokens)
+            }
+        }
+    }
+}
+
+/// Describes how a column is located in the row.
+#[derive(Debug, Clone, Copy)]
+struct Lookup {
+    /// Fall back to a unicode aware case insensitive match when a name isn't found by `Row::get`.
+    case_insensitive: bool,
+    /// The position of this column in the `indices` passed to `from_row_indexed`, if any.
+    slot: Option<usize>,
+}
+
+/// A naming convention that can be applied to all columns of a struct with `#[from_row(rename_all = "..")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RenameRule {
+    /// `lowercase`
+    Lower,
+    /// `UPPERCASE`
+    Upper,
+    /// `PascalCase`
+    Pascal,
+    /// `camelCase`
+    Camel,
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `kebab-case`
+    Kebab,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    /// All supported rules paired with the name used to select them.
+    const ALL: &'static [(&'static str, RenameRule)] = &[
+        ("lowercase", RenameRule::Lower),
+        ("UPPERCASE", RenameRule::Upper),
+        ("PascalCase", RenameRule::Pascal),
+        ("camelCase", RenameRule::Camel),
+        ("snake_case", RenameRule::Snake),
+        ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+        ("kebab-case", RenameRule::Kebab),
+        ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebab),
+    ];
+
+    /// Applies this rule to a rust field name, which is expected to be in `snake_case`.
+    fn apply(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_owned(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut pascal = String::with_capacity(field.len());
+                let mut capitalize = true;
+
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+
+                pascal
+            }
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply(field);
+                let mut chars = pascal.chars();
+
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => pascal,
+                }
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+}
+
+impl FromMeta for RenameRule {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        RenameRule::ALL
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, rule)| *rule)
+            .ok_or_else(|| {
+                let expected = RenameRule::ALL
+                    .iter()
+                    .map(|(name, _)| format!("`{name}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                Error::custom(format!(
+                    "unknown rename rule `{value}`, expected one of {expected}"
+                ))
+            })
     }
 }
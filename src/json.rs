@@ -0,0 +1,220 @@
+//! Conversion of rows with an unknown shape into [`serde_json::Value`].
+
+use serde_json::{Map, Number, Value};
+use std::fmt::Write;
+use tokio_postgres::{
+    types::{FromSql, Type},
+    Error, Row,
+};
+
+/// Converts all columns of `row` into a json object, keyed by column name.
+///
+/// See [`try_row_to_json`] for how each column type is converted.
+///
+/// # Panics
+///
+/// panics if any of the columns could not be converted.
+pub fn row_to_json(row: &Row) -> Value {
+    try_row_to_json(row).expect("could not convert row to json")
+}
+
+/// Try's to convert all columns of `row` into a json object, keyed by column name.
+///
+/// Booleans, integers, floats, text and `json`/`jsonb` columns are converted to their json equivalent and `NULL`
+/// becomes `null`. `uuid`, `numeric`, `inet` and `cidr` columns become strings in the same format postgres prints
+/// them in, `numeric` as a string so no precision is lost. With the `chrono` feature `date`, `time`, `timestamp`
+/// and `timestamptz` columns become ISO 8601 strings. The bytes of columns of any other type, including `bytea`,
+/// become a hex string prefixed with `\x`, like postgres prints a `bytea`. When multiple columns have the same name,
+/// the last one wins.
+pub fn try_row_to_json(row: &Row) -> Result<Value, Error> {
+    let mut object = Map::with_capacity(row.len());
+
+    for (index, column) in row.columns().iter().enumerate() {
+        object.insert(
+            column.name().to_owned(),
+            column_to_json(row, index, column.type_())?,
+        );
+    }
+
+    Ok(Value::Object(object))
+}
+
+/// Converts the column at `index` with type `ty` into a json value.
+fn column_to_json(row: &Row, index: usize, ty: &Type) -> Result<Value, Error> {
+    let value = match *ty {
+        Type::BOOL => row.try_get::<_, Option<bool>>(index)?.map(Value::Bool),
+        Type::INT2 => row.try_get::<_, Option<i16>>(index)?.map(Value::from),
+        Type::INT4 => row.try_get::<_, Option<i32>>(index)?.map(Value::from),
+        Type::INT8 => row.try_get::<_, Option<i64>>(index)?.map(Value::from),
+        Type::OID => row.try_get::<_, Option<u32>>(index)?.map(Value::from),
+        Type::FLOAT4 => row
+            .try_get::<_, Option<f32>>(index)?
+            .and_then(|float| Number::from_f64(float.into()))
+            .map(Value::Number),
+        Type::FLOAT8 => row
+            .try_get::<_, Option<f64>>(index)?
+            .and_then(Number::from_f64)
+            .map(Value::Number),
+        Type::JSON | Type::JSONB => row.try_get::<_, Option<Value>>(index)?,
+        #[cfg(feature = "chrono")]
+        Type::DATE => row
+            .try_get::<_, Option<chrono::NaiveDate>>(index)?
+            .map(|date| Value::String(date.format("%Y-%m-%d").to_string())),
+        #[cfg(feature = "chrono")]
+        Type::TIME => row
+            .try_get::<_, Option<chrono::NaiveTime>>(index)?
+            .map(|time| Value::String(time.format("%H:%M:%S%.f").to_string())),
+        #[cfg(feature = "chrono")]
+        Type::TIMESTAMP => row
+            .try_get::<_, Option<chrono::NaiveDateTime>>(index)?
+            .map(|timestamp| Value::String(timestamp.format("%Y-%m-%dT%H:%M:%S%.f").to_string())),
+        #[cfg(feature = "chrono")]
+        Type::TIMESTAMPTZ => row
+            .try_get::<_, Option<chrono::DateTime<chrono::Utc>>>(index)?
+            .map(|timestamp| {
+                Value::String(timestamp.format("%Y-%m-%dT%H:%M:%S%.f%:z").to_string())
+            }),
+        _ if <String as FromSql>::accepts(ty) => {
+            row.try_get::<_, Option<String>>(index)?.map(Value::String)
+        }
+        _ => row
+            .try_get::<_, Option<Text>>(index)?
+            .map(|text| Value::String(text.0)),
+    };
+
+    Ok(value.unwrap_or(Value::Null))
+}
+
+/// The value of a column that has no json equivalent, formatted as text.
+///
+/// `uuid`, `numeric`, `inet` and `cidr` are decoded from their binary format, the bytes of any other type are
+/// formatted as hex.
+struct Text(String);
+
+impl<'a> FromSql<'a> for Text {
+    fn from_sql(
+        ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        let text = match *ty {
+            Type::UUID => uuid_to_string(raw)?,
+            Type::NUMERIC => numeric_to_string(raw)?,
+            Type::INET | Type::CIDR => inet_to_string(raw, *ty == Type::CIDR)?,
+            _ => {
+                let mut text = String::with_capacity(2 + raw.len() * 2);
+                text.push_str("\\x");
+                raw.iter()
+                    .for_each(|byte| write!(text, "{byte:02x}").unwrap());
+                text
+            }
+        };
+
+        Ok(Text(text))
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+}
+
+/// Formats the 16 bytes of a `uuid` as `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`.
+fn uuid_to_string(raw: &[u8]) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+    if raw.len() != 16 {
+        return Err("invalid message length: uuid size mismatch".into());
+    }
+
+    let mut text = String::with_capacity(36);
+
+    for (index, byte) in raw.iter().enumerate() {
+        if matches!(index, 4 | 6 | 8 | 10) {
+            text.push('-');
+        }
+
+        write!(text, "{byte:02x}").unwrap();
+    }
+
+    Ok(text)
+}
+
+/// Formats the binary format of a `numeric`, a header followed by base 10000 digits, the way postgres prints it.
+fn numeric_to_string(raw: &[u8]) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+    let field = |offset: usize| -> Result<u16, Box<dyn std::error::Error + Sync + Send>> {
+        raw.get(offset..offset + 2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+            .ok_or_else(|| "invalid message length: numeric size mismatch".into())
+    };
+
+    let count = field(0)? as usize;
+    let weight = field(2)? as i16 as isize;
+    let sign = field(4)?;
+    let scale = field(6)? as usize;
+    let digits = (0..count)
+        .map(|digit| field(8 + digit * 2))
+        .collect::<Result<Vec<_>, _>>()?;
+    let digit = |position: isize| {
+        usize::try_from(position)
+            .ok()
+            .and_then(|position| digits.get(position))
+            .copied()
+            .unwrap_or(0)
+    };
+
+    let mut text = match sign {
+        0x0000 => String::new(),
+        0x4000 => String::from("-"),
+        0xC000 => return Ok(String::from("NaN")),
+        0xD000 => return Ok(String::from("Infinity")),
+        0xF000 => return Ok(String::from("-Infinity")),
+        _ => return Err(format!("invalid numeric sign {sign:#x}").into()),
+    };
+
+    if weight < 0 {
+        text.push('0');
+    } else {
+        write!(text, "{}", digit(0)).unwrap();
+        (1..=weight).for_each(|position| write!(text, "{:04}", digit(position)).unwrap());
+    }
+
+    if scale > 0 {
+        let mut fraction = String::with_capacity(scale + 4);
+        let mut position = weight + 1;
+
+        while fraction.len() < scale {
+            write!(fraction, "{:04}", digit(position)).unwrap();
+            position += 1;
+        }
+
+        fraction.truncate(scale);
+        text.push('.');
+        text.push_str(&fraction);
+    }
+
+    Ok(text)
+}
+
+/// Formats the binary format of an `inet` or `cidr` as an address followed by the netmask, which postgres omits
+/// for an `inet` that is a single host.
+fn inet_to_string(
+    raw: &[u8],
+    cidr: bool,
+) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+    let (address, bits) = match raw {
+        [2, bits, _, 4, address @ ..] if address.len() == 4 => {
+            let address: [u8; 4] = address.try_into()?;
+            (std::net::IpAddr::from(address), *bits)
+        }
+        [3, bits, _, 16, address @ ..] if address.len() == 16 => {
+            let address: [u8; 16] = address.try_into()?;
+            (std::net::IpAddr::from(address), *bits)
+        }
+        _ => return Err("invalid inet value".into()),
+    };
+
+    let host = if address.is_ipv4() { 32 } else { 128 };
+
+    Ok(if cidr || bits != host {
+        format!("{address}/{bits}")
+    } else {
+        address.to_string()
+    })
+}
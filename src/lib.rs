@@ -1,20 +1,1370 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 
-pub use postgres_from_row_derive::FromRow;
+pub use postgres_from_row_derive::{FromGroupedRows, FromRow, FromRowScalar, ToRowParams};
 pub use tokio_postgres;
 
+#[cfg(feature = "sqlx")]
+pub use sqlx;
+
+mod map;
+mod rename;
+
+#[cfg(feature = "performance")]
+mod mapper;
+
+#[cfg(feature = "json")]
+mod json;
+
+#[cfg(feature = "json")]
+pub use json::{row_to_json, try_row_to_json};
+
+#[cfg(feature = "stream")]
+mod stream;
+
+#[cfg(feature = "stream")]
+pub use stream::RowStreamExt;
+
+pub use map::{row_to_map, try_row_to_map};
+pub use rename::RenameRule;
+
+#[cfg(feature = "performance")]
+pub use mapper::RowMapper;
+
 /// A trait that allows mapping rows from either [postgres](<https://docs.rs/postgres>) or [tokio-postgres](<https://docs.rs/tokio-postgres>), to other types.
+///
+/// Implementors only need to provide [`FromRow::from_row_with_prefix`] and [`FromRow::try_from_row_with_prefix`],
+/// all other methods are implemented in terms of these two.
 pub trait FromRow: Sized {
+    /// The names of the columns this type reads, followed by the columns of flattened types with their prefix prepended.
+    ///
+    /// Columns that are read by position or by a `from_row_fn` are not included. Empty by default,
+    /// `#[derive(FromRow)]` overrides this for all types except generic structs that flatten another type.
+    const COLUMNS: &'static [&'static str] = &[];
+
     /// Performce the conversion
     ///
     /// # Panics
     ///
     /// panics if the row does not contain the expected column names.
-    fn from_row(row: &tokio_postgres::Row) -> Self;
+    fn from_row(row: &tokio_postgres::Row) -> Self {
+        Self::from_row_with_prefix(row, "")
+    }
 
     /// Try's to perform the conversion.
     ///
     /// Will return an error if the row does not contain the expected column names.
-    fn try_from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error>;
+    fn try_from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        Self::try_from_row_with_prefix(row, "")
+    }
+
+    /// Performce the conversion, and returns `None` when [`FromRow::try_from_row`] fails because a column it reads is
+    /// missing, see [`is_column_error`].
+    ///
+    /// This tells a row of a different shape apart from a row with invalid data. Columns of fields with a default
+    /// aren't required, while a field with `get_or_default` requires its column like it does in `try_from_row`.
+    ///
+    /// # Panics
+    ///
+    /// panics if a column is present but can't be converted.
+    fn from_row_opt(row: &tokio_postgres::Row) -> Option<Self> {
+        match Self::try_from_row(row) {
+            Err(error) if is_column_error(&error) => None,
+            Ok(value) => Some(value),
+            Err(error) => panic!("could not convert row: {error}"),
+        }
+    }
+
+    /// Try's to perform the conversion, and returns `Ok(None)` when it fails because a column it reads is missing,
+    /// see [`is_column_error`].
+    ///
+    /// Will return an error if a column is present but can't be converted.
+    fn try_from_row_opt(row: &tokio_postgres::Row) -> Result<Option<Self>, tokio_postgres::Error> {
+        match Self::try_from_row(row) {
+            Err(error) if is_column_error(&error) => Ok(None),
+            result => result.map(Some),
+        }
+    }
+
+    /// Performce the conversion of the row returned by `query_opt`, which is `None` when there was no row.
+    ///
+    /// Unlike [`FromRow::from_row_opt`] a row that is present is always converted.
+    ///
+    /// # Panics
+    ///
+    /// panics if the row does not contain the expected column names.
+    fn from_optional_row(row: Option<&tokio_postgres::Row>) -> Option<Self> {
+        row.map(Self::from_row)
+    }
+
+    /// Try's to perform the conversion of the row returned by `query_opt`, which is `None` when there was no row.
+    ///
+    /// Will return an error if the row does not contain the expected column names.
+    fn try_from_optional_row(
+        row: Option<&tokio_postgres::Row>,
+    ) -> Result<Option<Self>, tokio_postgres::Error> {
+        row.map(Self::try_from_row).transpose()
+    }
+
+    /// Performce the conversion, prepending `prefix` to every column name that is looked up.
+    ///
+    /// # Panics
+    ///
+    /// panics if the row does not contain the expected prefixed column names.
+    fn from_row_with_prefix(row: &tokio_postgres::Row, prefix: &str) -> Self;
+
+    /// Try's to perform the conversion, prepending `prefix` to every column name that is looked up.
+    ///
+    /// Will return an error if the row does not contain the expected prefixed column names.
+    fn try_from_row_with_prefix(
+        row: &tokio_postgres::Row,
+        prefix: &str,
+    ) -> Result<Self, tokio_postgres::Error>;
+
+    /// Performce the conversion like `from_row_with_prefix`, naming the columns of fields that don't have a name
+    /// or a naming convention of their own with `rename_all`.
+    ///
+    /// This is how `#[from_row(flatten, inherit_rename_all)]` passes a convention down, implementations that
+    /// aren't derived ignore it by default.
+    ///
+    /// # Panics
+    ///
+    /// panics if the row does not contain the expected prefixed column names.
+    fn from_row_with_rename_all(
+        row: &tokio_postgres::Row,
+        prefix: &str,
+        _rename_all: Option<RenameRule>,
+    ) -> Self {
+        Self::from_row_with_prefix(row, prefix)
+    }
+
+    /// Try's to perform the conversion like `try_from_row_with_prefix`, naming the columns of fields that don't
+    /// have a name or a naming convention of their own with `rename_all`.
+    ///
+    /// Will return an error if the row does not contain the expected prefixed column names.
+    fn try_from_row_with_rename_all(
+        row: &tokio_postgres::Row,
+        prefix: &str,
+        _rename_all: Option<RenameRule>,
+    ) -> Result<Self, tokio_postgres::Error> {
+        Self::try_from_row_with_prefix(row, prefix)
+    }
+
+    /// Checks that the `columns` of a statement contain every column this type requires, in the order they are read.
+    ///
+    /// Use this to fail fast before converting many rows of a statement. By default every set of columns is accepted,
+    /// `#[derive(FromRow)]` only overrides this when using `#[from_row(validate_columns)]`.
+    fn validate_columns(columns: &[tokio_postgres::Column]) -> Result<(), ColumnError> {
+        Self::validate_columns_with_prefix(columns, "")
+    }
+
+    /// Checks the `columns` of a statement like `validate_columns`, prepending `prefix` to every column name.
+    fn validate_columns_with_prefix(
+        _columns: &[tokio_postgres::Column],
+        _prefix: &str,
+    ) -> Result<(), ColumnError> {
+        Ok(())
+    }
+
+    /// Try's to perform the conversion, attempting every column instead of stopping at the first error.
+    ///
+    /// Returns the name of each column that could not be read, together with its error.
+    /// By default only the first error is reported, without a column name, `#[derive(FromRow)]` only
+    /// overrides this when using `#[from_row(verbose)]`.
+    fn try_from_row_verbose(
+        row: &tokio_postgres::Row,
+    ) -> Result<Self, Vec<(String, tokio_postgres::Error)>> {
+        Self::try_from_row_verbose_with_prefix(row, "")
+    }
+
+    /// Try's to perform the conversion like [`FromRow::try_from_row_verbose`], prepending `prefix` to every
+    /// column name that is looked up.
+    fn try_from_row_verbose_with_prefix(
+        row: &tokio_postgres::Row,
+        prefix: &str,
+    ) -> Result<Self, Vec<(String, tokio_postgres::Error)>> {
+        Self::try_from_row_with_prefix(row, prefix).map_err(|error| vec![(String::new(), error)])
+    }
+
+    /// Try's to perform the conversion, reporting the column and field that failed together with the error.
+    ///
+    /// By default the error doesn't contain a column or field, `#[derive(FromRow)]` only overrides this when
+    /// using `#[from_row(detailed)]`.
+    fn try_from_row_detailed(row: &tokio_postgres::Row) -> Result<Self, FromRowError> {
+        Self::try_from_row_detailed_with_prefix(row, "")
+    }
+
+    /// Try's to perform the conversion like [`FromRow::try_from_row_detailed`], prepending `prefix` to every
+    /// column name that is looked up.
+    fn try_from_row_detailed_with_prefix(
+        row: &tokio_postgres::Row,
+        prefix: &str,
+    ) -> Result<Self, FromRowError> {
+        Self::try_from_row_with_prefix(row, prefix).map_err(FromRowError::from)
+    }
+
+    /// Resolves the positions of the columns this type reads, so they can be reused for every row
+    /// returned by the same statement with [`FromRow::from_row_indexed`].
+    ///
+    /// By default nothing is resolved, `#[derive(FromRow)]` only overrides this when using `#[from_row(indexed)]`.
+    fn resolve_indices(_columns: &[tokio_postgres::Column]) -> ColumnIndices {
+        ColumnIndices::default()
+    }
+
+    /// Performce the conversion, using the column positions from [`FromRow::resolve_indices`].
+    /// Columns that were not resolved are looked up by name.
+    ///
+    /// # Panics
+    ///
+    /// panics if the row does not contain the expected column names.
+    fn from_row_indexed(row: &tokio_postgres::Row, _indices: &ColumnIndices) -> Self {
+        Self::from_row(row)
+    }
+
+    /// Try's to perform the conversion, using the column positions from [`FromRow::resolve_indices`].
+    /// Columns that were not resolved are looked up by name.
+    ///
+    /// Will return an error if the row does not contain the expected column names.
+    fn try_from_row_indexed(
+        row: &tokio_postgres::Row,
+        _indices: &ColumnIndices,
+    ) -> Result<Self, tokio_postgres::Error> {
+        Self::try_from_row(row)
+    }
+
+    /// Performs the conversion for each row.
+    ///
+    /// All rows are expected to be returned by the same statement, since the column positions are
+    /// only resolved once using the first row.
+    ///
+    /// # Panics
+    ///
+    /// panics if any of the rows does not contain the expected column names.
+    fn from_rows(rows: &[tokio_postgres::Row]) -> Vec<Self> {
+        let Some(first) = rows.first() else {
+            return Vec::new();
+        };
+
+        let indices = Self::resolve_indices(first.columns());
+
+        rows.iter()
+            .map(|row| Self::from_row_indexed(row, &indices))
+            .collect()
+    }
+
+    /// Try's to perform the conversion for each row.
+    ///
+    /// All rows are expected to be returned by the same statement, since the column positions are
+    /// only resolved once using the first row.
+    ///
+    /// Will return the first error encountered, without converting the remaining rows.
+    fn try_from_rows(rows: &[tokio_postgres::Row]) -> Result<Vec<Self>, tokio_postgres::Error> {
+        let Some(first) = rows.first() else {
+            return Ok(Vec::new());
+        };
+
+        let indices = Self::resolve_indices(first.columns());
+
+        rows.iter()
+            .map(|row| Self::try_from_row_indexed(row, &indices))
+            .collect()
+    }
+
+    /// Performce the conversion into an existing value, so a buffer can be reused for every row of a large result set.
+    ///
+    /// # Panics
+    ///
+    /// panics if the row does not contain the expected column names.
+    fn update_from_row(&mut self, row: &tokio_postgres::Row) {
+        self.update_from_row_with_prefix(row, "")
+    }
+
+    /// Try's to perform the conversion into an existing value.
+    ///
+    /// Will return an error if the row does not contain the expected column names, the value is left unchanged then.
+    fn try_update_from_row(
+        &mut self,
+        row: &tokio_postgres::Row,
+    ) -> Result<(), tokio_postgres::Error> {
+        self.try_update_from_row_with_prefix(row, "")
+    }
+
+    /// Performce the conversion into an existing value, prepending `prefix` to every column name that is looked up.
+    ///
+    /// By default this replaces the whole value. `#[derive(FromRow)]` overrides it for structs to assign the fields
+    /// that are read from the row one by one, skipped fields keep their value.
+    ///
+    /// # Panics
+    ///
+    /// panics if the row does not contain the expected column names.
+    fn update_from_row_with_prefix(&mut self, row: &tokio_postgres::Row, prefix: &str) {
+        *self = Self::from_row_with_prefix(row, prefix);
+    }
+
+    /// Try's to perform the conversion into an existing value, prepending `prefix` to every column name that is
+    /// looked up.
+    ///
+    /// Will return an error if the row does not contain the expected column names, the value is left unchanged then.
+    fn try_update_from_row_with_prefix(
+        &mut self,
+        row: &tokio_postgres::Row,
+        prefix: &str,
+    ) -> Result<(), tokio_postgres::Error> {
+        *self = Self::try_from_row_with_prefix(row, prefix)?;
+        Ok(())
+    }
+}
+
+/// A variant of [`FromRow`] for types that borrow from the row they are read from, such as a struct with a `&'a str` field.
+///
+/// `#[derive(FromRow)]` implements this trait instead of [`FromRow`] when the type of a field mentions one of the
+/// struct's lifetimes, the returned value is then tied to the lifetime of the row.
+pub trait FromBorrowedRow<'a>: Sized {
+    /// The names of the columns this type reads, followed by the columns of flattened types with their prefix prepended.
+    ///
+    /// See [`FromRow::COLUMNS`].
+    const COLUMNS: &'static [&'static str] = &[];
+
+    /// Performce the conversion
+    ///
+    /// # Panics
+    ///
+    /// panics if the row does not contain the expected column names.
+    fn from_row(row: &'a tokio_postgres::Row) -> Self {
+        Self::from_row_with_prefix(row, "")
+    }
+
+    /// Try's to perform the conversion.
+    ///
+    /// Will return an error if the row does not contain the expected column names.
+    fn try_from_row(row: &'a tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        Self::try_from_row_with_prefix(row, "")
+    }
+
+    /// See [`FromRow::from_row_opt`].
+    ///
+    /// # Panics
+    ///
+    /// panics if a column is present but can't be converted.
+    fn from_row_opt(row: &'a tokio_postgres::Row) -> Option<Self> {
+        match Self::try_from_row(row) {
+            Err(error) if is_column_error(&error) => None,
+            Ok(value) => Some(value),
+            Err(error) => panic!("could not convert row: {error}"),
+        }
+    }
+
+    /// See [`FromRow::try_from_row_opt`].
+    fn try_from_row_opt(
+        row: &'a tokio_postgres::Row,
+    ) -> Result<Option<Self>, tokio_postgres::Error> {
+        match Self::try_from_row(row) {
+            Err(error) if is_column_error(&error) => Ok(None),
+            result => result.map(Some),
+        }
+    }
+
+    /// See [`FromRow::from_optional_row`].
+    ///
+    /// # Panics
+    ///
+    /// panics if the row does not contain the expected column names.
+    fn from_optional_row(row: Option<&'a tokio_postgres::Row>) -> Option<Self> {
+        row.map(Self::from_row)
+    }
+
+    /// See [`FromRow::try_from_optional_row`].
+    fn try_from_optional_row(
+        row: Option<&'a tokio_postgres::Row>,
+    ) -> Result<Option<Self>, tokio_postgres::Error> {
+        row.map(Self::try_from_row).transpose()
+    }
+
+    /// Performce the conversion, prepending `prefix` to every column name that is looked up.
+    ///
+    /// # Panics
+    ///
+    /// panics if the row does not contain the expected prefixed column names.
+    fn from_row_with_prefix(row: &'a tokio_postgres::Row, prefix: &str) -> Self;
+
+    /// Try's to perform the conversion, prepending `prefix` to every column name that is looked up.
+    ///
+    /// Will return an error if the row does not contain the expected prefixed column names.
+    fn try_from_row_with_prefix(
+        row: &'a tokio_postgres::Row,
+        prefix: &str,
+    ) -> Result<Self, tokio_postgres::Error>;
+
+    /// Performce the conversion like `from_row_with_prefix`, naming the columns of fields that don't have a name
+    /// or a naming convention of their own with `rename_all`.
+    ///
+    /// This is how `#[from_row(flatten, inherit_rename_all)]` passes a convention down, implementations that
+    /// aren't derived ignore it by default.
+    ///
+    /// # Panics
+    ///
+    /// panics if the row does not contain the expected prefixed column names.
+    fn from_row_with_rename_all(
+        row: &'a tokio_postgres::Row,
+        prefix: &str,
+        _rename_all: Option<RenameRule>,
+    ) -> Self {
+        Self::from_row_with_prefix(row, prefix)
+    }
+
+    /// Try's to perform the conversion like `try_from_row_with_prefix`, naming the columns of fields that don't
+    /// have a name or a naming convention of their own with `rename_all`.
+    ///
+    /// Will return an error if the row does not contain the expected prefixed column names.
+    fn try_from_row_with_rename_all(
+        row: &'a tokio_postgres::Row,
+        prefix: &str,
+        _rename_all: Option<RenameRule>,
+    ) -> Result<Self, tokio_postgres::Error> {
+        Self::try_from_row_with_prefix(row, prefix)
+    }
+
+    /// Performce the conversion for each row.
+    ///
+    /// # Panics
+    ///
+    /// panics if any of the rows does not contain the expected column names.
+    fn from_rows(rows: &'a [tokio_postgres::Row]) -> Vec<Self> {
+        rows.iter().map(Self::from_row).collect()
+    }
+
+    /// Try's to perform the conversion for each row.
+    ///
+    /// Will return the first error encountered, without converting the remaining rows.
+    fn try_from_rows(rows: &'a [tokio_postgres::Row]) -> Result<Vec<Self>, tokio_postgres::Error> {
+        rows.iter().map(Self::try_from_row).collect()
+    }
+}
+
+/// A trait that converts a type into query parameters, the inverse of [`FromRow`].
+///
+/// The parameters are in the same order as [`ToRowParams::PARAM_COLUMNS`], so both can be used to build an `INSERT`
+/// statement that stays in sync with the struct.
+pub trait ToRowParams {
+    /// The names of the columns, in the same order as the parameters returned by [`ToRowParams::params`].
+    const PARAM_COLUMNS: &'static [&'static str];
+
+    /// Returns the value of every column as a query parameter.
+    fn params(&self) -> Vec<&(dyn tokio_postgres::types::ToSql + Sync)>;
+}
+
+/// A trait that maps the rows of a join to parents which each hold the children of their rows.
+///
+/// Consecutive rows with the same group key belong to the same parent, so the query should order the rows by that key.
+/// The other fields of a parent are read from the first row of its group.
+pub trait FromGroupedRows: Sized {
+    /// Performce the conversion
+    ///
+    /// # Panics
+    ///
+    /// panics if a row does not contain the expected column names.
+    fn from_grouped_rows(rows: &[tokio_postgres::Row]) -> Vec<Self>;
+
+    /// Try's to perform the conversion.
+    ///
+    /// Will return an error if a row does not contain the expected column names.
+    fn try_from_grouped_rows(
+        rows: &[tokio_postgres::Row],
+    ) -> Result<Vec<Self>, tokio_postgres::Error>;
+}
+
+/// Implements `FromRow` for a tuple, reading each element from the column at the same position.
+macro_rules! impl_from_row_for_tuple {
+    ($($index:tt => $ty:ident),+) => {
+        impl<$($ty),+> FromRow for ($($ty,)+)
+        where
+            $($ty: for<'a> tokio_postgres::types::FromSql<'a>,)+
+        {
+            fn from_row_with_prefix(row: &tokio_postgres::Row, _prefix: &str) -> Self {
+                ($(row.get::<usize, $ty>($index),)+)
+            }
+
+            fn try_from_row_with_prefix(
+                row: &tokio_postgres::Row,
+                _prefix: &str,
+            ) -> Result<Self, tokio_postgres::Error> {
+                Ok(($(row.try_get::<usize, $ty>($index)?,)+))
+            }
+        }
+    };
+}
+
+impl_from_row_for_tuple!(0 => A);
+impl_from_row_for_tuple!(0 => A, 1 => B);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H, 8 => I);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H, 8 => I, 9 => J);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H, 8 => I, 9 => J, 10 => K);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H, 8 => I, 9 => J, 10 => K, 11 => L);
+
+/// Implemented by `#[derive(FromRow)]` for types with `#[from_row(indexed)]`, whose [`FromRow::resolve_indices`]
+/// resolves the positions of their columns instead of leaving every column to be looked up by name.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` doesn't resolve the positions of its columns",
+    label = "`RowMapper` requires `#[from_row(indexed)]`",
+    note = "add `#[from_row(indexed)]` to the struct `{Self}`"
+)]
+pub trait Indexed: FromRow {}
+
+/// The positions of the columns read by a type, see [`FromRow::resolve_indices`].
+#[derive(Debug, Clone, Default)]
+pub struct ColumnIndices(Vec<Option<usize>>);
+
+impl ColumnIndices {
+    /// Returns the position of the `n`th column that was resolved, or `None` if it isn't present.
+    pub fn get(&self, n: usize) -> Option<usize> {
+        self.0.get(n).copied().flatten()
+    }
+}
+
+/// The reason the columns of a statement don't match a type, see [`FromRow::validate_columns`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnError {
+    /// The column with this name is not present.
+    Missing(String),
+    /// The column `column` is present before the column `before`, while it's read after it.
+    OutOfOrder {
+        /// The name of the column that is out of order.
+        column: String,
+        /// The name of the column that is read before `column`.
+        before: String,
+    },
+}
+
+impl std::fmt::Display for ColumnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColumnError::Missing(column) => write!(f, "column {column:?} is missing"),
+            ColumnError::OutOfOrder { column, before } => {
+                write!(f, "column {column:?} is present before column {before:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ColumnError {}
+
+/// Returns wether `error`, as returned by `try_from_row`, was caused by a column that is missing from the row, rather
+/// than by a value that could not be converted.
+///
+/// `tokio_postgres::Error` doesn't expose its kind. A missing column is the only error of `Row::try_get` without a
+/// source, whose message is `invalid column ..`, so this relies on both.
+pub fn is_column_error(error: &tokio_postgres::Error) -> bool {
+    std::error::Error::source(error).is_none() && error.to_string().starts_with("invalid column")
+}
+
+/// The error returned by [`FromRow::try_from_row_detailed`], a `tokio_postgres::Error` together with the
+/// column and field that could not be read.
+#[derive(Debug)]
+pub struct FromRowError {
+    column: String,
+    field: String,
+    source: tokio_postgres::Error,
+}
+
+impl FromRowError {
+    /// The name of the column that could not be read, this is empty when the column is not known.
+    pub fn column(&self) -> &str {
+        &self.column
+    }
+
+    /// The name of the field that could not be read, this is empty when the field is not known.
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Returns the underlying `tokio_postgres::Error`, for use where only that error is expected.
+    pub fn into_inner(self) -> tokio_postgres::Error {
+        self.source
+    }
+}
+
+impl From<tokio_postgres::Error> for FromRowError {
+    fn from(source: tokio_postgres::Error) -> Self {
+        Self {
+            column: String::new(),
+            field: String::new(),
+            source,
+        }
+    }
+}
+
+impl std::fmt::Display for FromRowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.column.is_empty(), self.field.is_empty()) {
+            (true, true) => write!(f, "{}", self.source),
+            (true, false) => write!(f, "error reading field `{}`: {}", self.field, self.source),
+            (false, _) => write!(
+                f,
+                "error reading column {:?} into field `{}`: {}",
+                self.column, self.field, self.source
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FromRowError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Invoked by the derive for every field with an attribute that needs the `chrono` feature, so a missing feature is
+/// reported by name instead of as a missing type in `__private`.
+#[cfg(feature = "chrono")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __from_row_requires_chrono {
+    ($attribute:literal) => {};
+}
+
+#[cfg(not(feature = "chrono"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __from_row_requires_chrono {
+    ($attribute:literal) => {
+        compile_error!(concat!(
+            "`",
+            $attribute,
+            "` requires the `chrono` feature of postgres-from-row"
+        ));
+    };
+}
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __from_row_requires_serde {
+    ($attribute:literal) => {};
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __from_row_requires_serde {
+    ($attribute:literal) => {
+        compile_error!(concat!(
+            "`",
+            $attribute,
+            "` requires the `serde` feature of postgres-from-row"
+        ));
+    };
+}
+
+#[cfg(feature = "uuid")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __from_row_requires_uuid {
+    ($attribute:literal) => {};
+}
+
+#[cfg(not(feature = "uuid"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __from_row_requires_uuid {
+    ($attribute:literal) => {
+        compile_error!(concat!(
+            "`",
+            $attribute,
+            "` requires the `uuid` feature of postgres-from-row"
+        ));
+    };
+}
+
+#[cfg(feature = "rust_decimal")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __from_row_requires_rust_decimal {
+    ($attribute:literal) => {};
+}
+
+#[cfg(not(feature = "rust_decimal"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __from_row_requires_rust_decimal {
+    ($attribute:literal) => {
+        compile_error!(concat!(
+            "`",
+            $attribute,
+            "` requires the `rust_decimal` feature of postgres-from-row"
+        ));
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    //! Helpers used by the code generated by `#[derive(FromRow)]`, these are not part of the public api.
+
+    use crate::{ColumnError, ColumnIndices, FromRowError, RenameRule};
+    use std::{borrow::Cow, error::Error, marker::PhantomData};
+    use tokio_postgres::{
+        types::{Field, FromSql, Kind, Type},
+        Column, Row,
+    };
+
+    /// Checks wether `row` contains the column `name`, using the same lookup rules as `Row::get`,
+    /// which falls back to a case insensitive match.
+    pub fn has_column(row: &Row, name: &str) -> bool {
+        row.columns()
+            .iter()
+            .any(|column| column.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Unwraps the result of a fallible conversion in `from_row`, such as `try_from_fn`, `try_from` or
+    /// `try_from_row_fn`, panicking with the error and the `field` it was read into.
+    pub fn expect_converted<T, E: std::fmt::Debug>(result: Result<T, E>, field: &str) -> T {
+        match result {
+            Ok(value) => value,
+            Err(error) => panic!("conversion failed for {field}: {error:?}"),
+        }
+    }
+
+    /// Reads every column of `row` that starts with `prefix` and isn't read by another field, keyed by the name of
+    /// the column without the prefix.
+    ///
+    /// The columns that are read are the `known` columns and the first present name of each of the `candidates`,
+    /// which are resolved to a position with the same lookup rules as the other fields.
+    pub fn rest_columns<'a, T, M>(
+        row: &'a Row,
+        prefix: &str,
+        known: &[&str],
+        candidates: &[&[&str]],
+        case_insensitive: bool,
+    ) -> Result<M, tokio_postgres::Error>
+    where
+        T: FromSql<'a>,
+        M: FromIterator<(String, T)>,
+    {
+        let columns = row.columns();
+        let find = |name: &&str| position(columns, &prefixed(prefix, name), case_insensitive);
+        let read: Vec<usize> = known
+            .iter()
+            .filter_map(find)
+            .chain(
+                candidates
+                    .iter()
+                    .filter_map(|names| names.iter().find_map(find)),
+            )
+            .collect();
+
+        columns
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !read.contains(index))
+            .filter_map(|(index, column)| Some((index, column.name().strip_prefix(prefix)?)))
+            .map(|(index, name)| Ok((name.to_owned(), row.try_get(index)?)))
+            .collect()
+    }
+
+    /// Finds the index of the column `name`, using the same lookup rules as `Row::get` and then falling back
+    /// to a unicode aware case insensitive match.
+    pub fn find_column(columns: &[Column], name: &str) -> Option<usize> {
+        columns
+            .iter()
+            .position(|column| column.name() == name)
+            .or_else(|| {
+                columns
+                    .iter()
+                    .position(|column| column.name().eq_ignore_ascii_case(name))
+            })
+            .or_else(|| {
+                let name = name.to_lowercase();
+
+                columns
+                    .iter()
+                    .position(|column| column.name().to_lowercase() == name)
+            })
+    }
+
+    /// Resolves the position of each of the column `names`, using either the same lookup rules as `Row::get`,
+    /// or the ones of [`find_column`] when `case_insensitive` is set.
+    pub fn resolve_indices(
+        columns: &[Column],
+        names: &[&str],
+        case_insensitive: bool,
+    ) -> ColumnIndices {
+        let indices = names
+            .iter()
+            .map(|name| position(columns, name, case_insensitive));
+
+        ColumnIndices(indices.collect())
+    }
+
+    /// Finds the position of the column `name`, using either the same lookup rules as `Row::get`,
+    /// or the ones of [`find_column`] when `case_insensitive` is set.
+    fn position(columns: &[Column], name: &str, case_insensitive: bool) -> Option<usize> {
+        if case_insensitive {
+            find_column(columns, name)
+        } else {
+            columns
+                .iter()
+                .position(|column| column.name() == name)
+                .or_else(|| {
+                    columns
+                        .iter()
+                        .position(|column| column.name().eq_ignore_ascii_case(name))
+                })
+        }
+    }
+
+    /// Checks that each of the `expected` columns is present in `columns`, and that they are present in the same order.
+    ///
+    /// Every expected column is a list of candidate names of which the first present one is used,
+    /// and all names are prefixed with `prefix`.
+    pub fn validate_columns(
+        columns: &[Column],
+        prefix: &str,
+        expected: &[&[&str]],
+        case_insensitive: bool,
+    ) -> Result<(), ColumnError> {
+        let mut previous: Option<(usize, Cow<'_, str>)> = None;
+
+        for candidates in expected {
+            let found = candidates.iter().find_map(|name| {
+                let name = prefixed(prefix, name);
+                position(columns, &name, case_insensitive).map(|index| (index, name))
+            });
+
+            let Some((index, name)) = found else {
+                let last = candidates.last().copied().unwrap_or_default();
+                return Err(ColumnError::Missing(prefixed(prefix, last).into_owned()));
+            };
+
+            if let Some((before, before_name)) = &previous {
+                if index < *before {
+                    return Err(ColumnError::OutOfOrder {
+                        column: name.into_owned(),
+                        before: before_name.to_string(),
+                    });
+                }
+            }
+
+            previous = Some((index, name));
+        }
+
+        Ok(())
+    }
+
+    /// Prepends `prefix` to the column `name`, only allocating when the prefix is not empty.
+    pub fn prefixed<'a>(prefix: &str, name: &'a str) -> Cow<'a, str> {
+        if prefix.is_empty() {
+            Cow::Borrowed(name)
+        } else {
+            Cow::Owned([prefix, name].concat())
+        }
+    }
+
+    /// Adds the `column` and `field` that could not be read to `source`.
+    pub fn detailed(column: String, field: &str, source: tokio_postgres::Error) -> FromRowError {
+        FromRowError {
+            column,
+            field: field.to_owned(),
+            source,
+        }
+    }
+
+    /// Returns the prefixed name of the column of `field`, which is read from the column `name` unless the struct
+    /// is flattened with a naming convention in `rename_all`.
+    ///
+    /// `name` ends with `field` and starts with the prefix of the struct, which is kept when a convention is applied.
+    pub fn renamed<'a>(
+        prefix: &str,
+        rename_all: Option<RenameRule>,
+        name: &'a str,
+        field: &str,
+    ) -> Cow<'a, str> {
+        match rename_all {
+            Some(rule) => {
+                let own = &name[..name.len() - field.len()];
+                Cow::Owned(format!("{prefix}{own}{}", rule.apply(field)))
+            }
+            None => prefixed(prefix, name),
+        }
+    }
+
+    /// Returns the first of the prefixed `names` that is present in `row`. When none are present the last name is
+    /// returned, so that reading it reports that column as missing.
+    pub fn first_present<'a>(row: &Row, prefix: &str, names: &[&'a str]) -> Cow<'a, str> {
+        let mut names = names.iter().map(|name| prefixed(prefix, name));
+        let mut last = names.next().unwrap_or_default();
+
+        for name in names {
+            if has_column(row, &last) {
+                break;
+            }
+
+            last = name;
+        }
+
+        last
+    }
+
+    /// Returns the number of columns in `parts`, where each part is a prefix and the columns it's prepended to.
+    pub const fn columns_count(parts: &[(&str, &[&str])]) -> usize {
+        let mut count = 0;
+        let mut part = 0;
+
+        while part < parts.len() {
+            count += parts[part].1.len();
+            part += 1;
+        }
+
+        count
+    }
+
+    /// Returns the number of bytes needed to store all prefixed columns in `parts`.
+    pub const fn columns_byte_len(parts: &[(&str, &[&str])]) -> usize {
+        let mut len = 0;
+        let mut part = 0;
+
+        while part < parts.len() {
+            let (prefix, columns) = parts[part];
+            let mut column = 0;
+
+            while column < columns.len() {
+                len += prefix.len() + columns[column].len();
+                column += 1;
+            }
+
+            part += 1;
+        }
+
+        len
+    }
+
+    /// Writes all prefixed columns in `parts` into a single buffer, to be split up by [`columns_split`].
+    pub const fn columns_bytes<const N: usize>(parts: &[(&str, &[&str])]) -> [u8; N] {
+        const fn copy<const N: usize>(bytes: &mut [u8; N], at: usize, value: &str) -> usize {
+            let value = value.as_bytes();
+            let mut index = 0;
+
+            while index < value.len() {
+                bytes[at + index] = value[index];
+                index += 1;
+            }
+
+            at + value.len()
+        }
+
+        let mut bytes = [0; N];
+        let mut at = 0;
+        let mut part = 0;
+
+        while part < parts.len() {
+            let (prefix, columns) = parts[part];
+            let mut column = 0;
+
+            while column < columns.len() {
+                at = copy(&mut bytes, at, prefix);
+                at = copy(&mut bytes, at, columns[column]);
+                column += 1;
+            }
+
+            part += 1;
+        }
+
+        bytes
+    }
+
+    /// Splits the buffer written by [`columns_bytes`] back into the prefixed columns of `parts`.
+    pub const fn columns_split<const N: usize>(
+        mut bytes: &'static [u8],
+        parts: &[(&str, &[&str])],
+    ) -> [&'static str; N] {
+        let mut split = [""; N];
+        let mut index = 0;
+        let mut part = 0;
+
+        while part < parts.len() {
+            let (prefix, columns) = parts[part];
+            let mut column = 0;
+
+            while column < columns.len() {
+                let (name, rest) = bytes.split_at(prefix.len() + columns[column].len());
+
+                split[index] = match std::str::from_utf8(name) {
+                    Ok(name) => name,
+                    Err(_) => panic!("column names are valid utf-8"),
+                };
+
+                bytes = rest;
+                index += 1;
+                column += 1;
+            }
+
+            part += 1;
+        }
+
+        split
+    }
+
+    /// Accepts a column of any type without decoding it, used to check wether a column is `NULL`.
+    pub struct Ignored;
+
+    impl<'a> FromSql<'a> for Ignored {
+        fn from_sql(_ty: &Type, _raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            Ok(Ignored)
+        }
+
+        fn accepts(_ty: &Type) -> bool {
+            true
+        }
+    }
+
+    /// Reads a postgres array into a fixed size array, failing when the number of elements is not `N`.
+    pub struct FixedArray<T, const N: usize>(pub [T; N]);
+
+    impl<'a, T: FromSql<'a>, const N: usize> FromSql<'a> for FixedArray<T, N> {
+        fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            let elements = Vec::<T>::from_sql(ty, raw)?;
+            let len = elements.len();
+
+            match elements.try_into() {
+                Ok(array) => Ok(FixedArray(array)),
+                Err(_) => Err(format!("expected an array of {N} elements, got {len}").into()),
+            }
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            <Vec<T> as FromSql>::accepts(ty)
+        }
+    }
+
+    /// Reads a `timestamptz` or a `timestamp` column into a `DateTime<Utc>`, a timestamp without a time zone
+    /// is taken to be in UTC.
+    #[cfg(feature = "chrono")]
+    pub struct UtcDateTime(pub chrono::DateTime<chrono::Utc>);
+
+    #[cfg(feature = "chrono")]
+    impl<'a> FromSql<'a> for UtcDateTime {
+        fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            if *ty == Type::TIMESTAMP {
+                let naive = chrono::NaiveDateTime::from_sql(ty, raw)?;
+                Ok(UtcDateTime(naive.and_utc()))
+            } else {
+                chrono::DateTime::<chrono::Utc>::from_sql(ty, raw).map(UtcDateTime)
+            }
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            matches!(*ty, Type::TIMESTAMP | Type::TIMESTAMPTZ)
+        }
+    }
+
+    /// Reads the text of a postgres enum or a text column, parsing it with the `FromStr` implementation of `T`.
+    pub struct Parsed<T>(pub T);
+
+    impl<'a, T> FromSql<'a> for Parsed<T>
+    where
+        T: std::str::FromStr,
+        T::Err: Into<Box<dyn Error + Sync + Send>>,
+    {
+        fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            let text = if matches!(ty.kind(), Kind::Enum(_)) {
+                std::str::from_utf8(raw)?
+            } else {
+                <&str>::from_sql(ty, raw)?
+            };
+
+            text.parse().map(Parsed).map_err(Into::into)
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            matches!(ty.kind(), Kind::Enum(_)) || <&str as FromSql>::accepts(ty)
+        }
+    }
+
+    /// The delimiter of `#[from_row(split = "..")]`, implemented by a type that is generated for every delimiter.
+    pub trait Delimiter {
+        /// The text that separates the values.
+        const DELIMITER: &'static str;
+    }
+
+    /// Reads a text column of values separated by `D::DELIMITER` into a `Vec<T>`, parsing every value with `FromStr`.
+    /// An empty string is an empty `Vec`.
+    pub struct Split<T, D>(pub Vec<T>, PhantomData<D>);
+
+    impl<'a, T, D> FromSql<'a> for Split<T, D>
+    where
+        T: std::str::FromStr,
+        T::Err: Into<Box<dyn Error + Sync + Send>>,
+        D: Delimiter,
+    {
+        fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            let text = <&str>::from_sql(ty, raw)?;
+
+            if text.is_empty() {
+                return Ok(Split(Vec::new(), PhantomData));
+            }
+
+            text.split(D::DELIMITER)
+                .map(|value| value.parse().map_err(Into::into))
+                .collect::<Result<_, _>>()
+                .map(|values| Split(values, PhantomData))
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            <&str as FromSql>::accepts(ty)
+        }
+    }
+
+    /// Reads a column as `S` and converts it into `T` with `TryFrom`, such as an `INT8` column into a `u32`.
+    pub struct Cast<T, S>(pub T, std::marker::PhantomData<S>);
+
+    impl<'a, T, S> FromSql<'a> for Cast<T, S>
+    where
+        S: FromSql<'a>,
+        T: TryFrom<S>,
+        T::Error: Into<Box<dyn Error + Sync + Send>>,
+    {
+        fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            let value = S::from_sql(ty, raw)?;
+            T::try_from(value)
+                .map(|value| Cast(value, std::marker::PhantomData))
+                .map_err(Into::into)
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            S::accepts(ty)
+        }
+    }
+
+    /// Reads a text column that contains a UUID, parsing it with `Uuid::parse_str`.
+    #[cfg(feature = "uuid")]
+    pub struct UuidText(pub uuid::Uuid);
+
+    #[cfg(feature = "uuid")]
+    impl<'a> FromSql<'a> for UuidText {
+        fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            let text = <&str>::from_sql(ty, raw)?;
+            Ok(UuidText(uuid::Uuid::parse_str(text)?))
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            <&str as FromSql>::accepts(ty)
+        }
+    }
+
+    /// Reads the EWKB bytes of a PostGIS `geometry` or `geography` column, or the bytes of a `bytea` column.
+    pub struct Wkb(pub Vec<u8>);
+
+    impl<'a> FromSql<'a> for Wkb {
+        fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            Ok(Wkb(raw.to_vec()))
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            *ty == Type::BYTEA || matches!(ty.name(), "geometry" | "geography")
+        }
+    }
+
+    /// Reads a `NUMERIC` or `MONEY` column into a `Decimal`, or parses a text column with `Decimal::from_str`.
+    ///
+    /// `MONEY` is stored as a number of cents, this assumes `lc_monetary` uses two fractional digits.
+    #[cfg(feature = "rust_decimal")]
+    pub struct Numeric(pub rust_decimal::Decimal);
+
+    #[cfg(feature = "rust_decimal")]
+    impl<'a> FromSql<'a> for Numeric {
+        fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            let value = match *ty {
+                Type::NUMERIC => rust_decimal::Decimal::from_sql(ty, raw)?,
+                Type::MONEY => rust_decimal::Decimal::new(i64::from_sql(&Type::INT8, raw)?, 2),
+                _ => <&str>::from_sql(ty, raw)?.parse()?,
+            };
+
+            Ok(Numeric(value))
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            matches!(*ty, Type::NUMERIC | Type::MONEY) || <&str as FromSql>::accepts(ty)
+        }
+    }
+
+    /// Reads an `INTERVAL` column into a `Duration`, counting a day as 24 hours.
+    ///
+    /// Months have no fixed length and a `Duration` can't be negative, so both fail to convert.
+    pub struct Interval(pub std::time::Duration);
+
+    impl<'a> FromSql<'a> for Interval {
+        fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            let raw: [u8; 16] = raw
+                .try_into()
+                .map_err(|_| "invalid message length: interval size mismatch")?;
+            let microseconds = i64::from_be_bytes(raw[..8].try_into()?);
+            let days = i32::from_be_bytes(raw[8..12].try_into()?);
+            let months = i32::from_be_bytes(raw[12..].try_into()?);
+
+            if months != 0 {
+                return Err(format!(
+                    "can't convert an interval of {months} months into a `Duration`"
+                )
+                .into());
+            }
+
+            let microseconds = i128::from(days) * 86_400_000_000 + i128::from(microseconds);
+            let microseconds = u64::try_from(microseconds)
+                .map_err(|_| "can't convert a negative interval into a `Duration`")?;
+
+            Ok(Interval(std::time::Duration::from_micros(microseconds)))
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            *ty == Type::INTERVAL
+        }
+    }
+
+    /// Reads a text column that contains JSON, deserializing it with `serde_json::from_str`.
+    #[cfg(feature = "serde")]
+    pub struct JsonText<T>(pub T);
+
+    #[cfg(feature = "serde")]
+    impl<'a, T: serde::de::DeserializeOwned> FromSql<'a> for JsonText<T> {
+        fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            let text = <&str>::from_sql(ty, raw)?;
+            Ok(JsonText(serde_json::from_str(text)?))
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            <&str as FromSql>::accepts(ty)
+        }
+    }
+
+    /// The known values of the tag column of an enum that derives `FromRow`.
+    pub trait Tags {
+        /// The tag of each variant, in declaration order.
+        const TAGS: &'static [&'static str];
+    }
+
+    /// Reads the tag column of an enum, resolving it to the position of the tag in `T::TAGS`.
+    /// Unknown tags fail to convert, just like a column of the wrong type.
+    pub struct Tag<T>(pub usize, PhantomData<T>);
+
+    impl<'a, T: Tags> FromSql<'a> for Tag<T> {
+        fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            let tag = <&str as FromSql>::from_sql(ty, raw)?;
+
+            match T::TAGS.iter().position(|known| *known == tag) {
+                Some(index) => Ok(Tag(index, PhantomData)),
+                None => Err(format!(
+                    "unknown tag `{tag}`, expected one of {}",
+                    T::TAGS
+                        .iter()
+                        .map(|known| format!("`{known}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+                .into()),
+            }
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            <&str as FromSql>::accepts(ty)
+        }
+    }
+
+    /// The fields of a value of a composite type, as used by `#[from_row(composite)]`.
+    pub struct Composite<'t, 'a> {
+        fields: Vec<(&'t Field, Option<&'a [u8]>)>,
+    }
+
+    impl<'t, 'a> Composite<'t, 'a> {
+        /// Splits the binary representation of a value of the composite type `ty` into its fields.
+        pub fn new(ty: &'t Type, mut raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            let Kind::Composite(fields) = ty.kind() else {
+                return Err(format!("`{ty}` is not a composite type").into());
+            };
+
+            let count = read_i32(&mut raw)?;
+
+            if usize::try_from(count).ok() != Some(fields.len()) {
+                return Err(format!(
+                    "expected {} fields for composite type `{ty}`, got {count}",
+                    fields.len()
+                )
+                .into());
+            }
+
+            let mut values = Vec::with_capacity(fields.len());
+
+            for field in fields {
+                let _oid = read_i32(&mut raw)?;
+                let len = read_i32(&mut raw)?;
+
+                let value = match usize::try_from(len) {
+                    Ok(len) if len <= raw.len() => {
+                        let (value, rest) = raw.split_at(len);
+                        raw = rest;
+                        Some(value)
+                    }
+                    Ok(_) => return Err("unexpected end of composite value".into()),
+                    Err(_) => None,
+                };
+
+                values.push((field, value));
+            }
+
+            Ok(Composite { fields: values })
+        }
+
+        /// Wether the composite type contains the field `name`.
+        pub fn has(&self, name: &str) -> bool {
+            self.fields.iter().any(|(field, _)| field.name() == name)
+        }
+
+        /// Decodes the field `name`.
+        pub fn get<T: FromSql<'a>>(&self, name: &str) -> Result<T, Box<dyn Error + Sync + Send>> {
+            match self.fields.iter().find(|(field, _)| field.name() == name) {
+                Some((field, value)) => T::from_sql_nullable(field.type_(), *value),
+                None => Err(format!("composite field `{name}` is not present").into()),
+            }
+        }
+    }
+
+    /// Reads a big endian `i32` from the start of `raw`.
+    fn read_i32(raw: &mut &[u8]) -> Result<i32, Box<dyn Error + Sync + Send>> {
+        if raw.len() < 4 {
+            return Err("unexpected end of composite value".into());
+        }
+
+        let (bytes, rest) = raw.split_at(4);
+        *raw = rest;
+        Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Checks wether `ty` is a composite type with a field `name` that can be decoded as `T`, a missing field is
+    /// accepted when it's `optional`.
+    pub fn accepts_field<'a, T: FromSql<'a>>(ty: &Type, name: &str, optional: bool) -> bool {
+        let Kind::Composite(fields) = ty.kind() else {
+            return false;
+        };
+
+        match fields.iter().find(|field| field.name() == name) {
+            Some(field) => T::accepts(field.type_()),
+            None => optional,
+        }
+    }
+
+    /// Implemented by types with `#[from_row(composite)]`, required for the elements of `#[from_row(aggregate)]`.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` can't be read as a composite type",
+        label = "`#[from_row(aggregate)]` requires the elements to be composite types",
+        note = "add `#[from_row(composite)]` to the struct `{Self}`"
+    )]
+    pub trait FromComposite {}
+
+    /// Checks wether `ty` is a composite type.
+    pub fn is_composite(ty: &Type) -> bool {
+        matches!(ty.kind(), Kind::Composite(_))
+    }
 }
@@ -0,0 +1,49 @@
+//! Conversion of rows where every column has the same type into a [`HashMap`].
+
+use crate::FromRow;
+use std::collections::HashMap;
+use tokio_postgres::{types::FromSql, Error, Row};
+
+/// Converts all columns of `row` into a map, keyed by column name.
+///
+/// # Panics
+///
+/// panics if any of the columns could not be converted into `T`.
+pub fn row_to_map<T>(row: &Row) -> HashMap<String, T>
+where
+    T: for<'a> FromSql<'a>,
+{
+    try_row_to_map(row).expect("could not convert row to map")
+}
+
+/// Try's to convert all columns of `row` into a map, keyed by column name.
+///
+/// Columns are read by position, so columns with the same name are all converted, but only the last one is kept.
+pub fn try_row_to_map<T>(row: &Row) -> Result<HashMap<String, T>, Error>
+where
+    T: for<'a> FromSql<'a>,
+{
+    HashMap::try_from_row(row)
+}
+
+/// Reads every column whose name starts with the prefix, keyed by the column name without the prefix.
+impl<T> FromRow for HashMap<String, T>
+where
+    T: for<'a> FromSql<'a>,
+{
+    fn from_row_with_prefix(row: &Row, prefix: &str) -> Self {
+        Self::try_from_row_with_prefix(row, prefix).expect("could not convert row to map")
+    }
+
+    fn try_from_row_with_prefix(row: &Row, prefix: &str) -> Result<Self, Error> {
+        let mut map = HashMap::with_capacity(row.len());
+
+        for (index, column) in row.columns().iter().enumerate() {
+            if let Some(name) = column.name().strip_prefix(prefix) {
+                map.insert(name.to_owned(), row.try_get(index)?);
+            }
+        }
+
+        Ok(map)
+    }
+}
@@ -0,0 +1,79 @@
+//! A reusable mapper that keeps the column positions of a statement across batches of rows.
+
+use crate::{ColumnIndices, Indexed};
+use std::marker::PhantomData;
+use tokio_postgres::{Column, Error, Row};
+
+/// Converts rows into `T` using the column positions resolved once from the columns of a statement.
+///
+/// Unlike [`FromRow::from_rows`](crate::FromRow::from_rows), which resolves the positions for every batch, a
+/// `RowMapper` can be kept around for all pages of a cursor or all executions of a prepared statement. `T` has to use
+/// `#[from_row(indexed)]`, since other types don't resolve any positions.
+pub struct RowMapper<T> {
+    indices: ColumnIndices,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Indexed> RowMapper<T> {
+    /// Resolves the positions of the columns `T` reads, such as the columns of a prepared `Statement`.
+    pub fn new(columns: &[Column]) -> Self {
+        RowMapper {
+            indices: T::resolve_indices(columns),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the resolved column positions.
+    pub fn indices(&self) -> &ColumnIndices {
+        &self.indices
+    }
+
+    /// Performs the conversion of a single row.
+    ///
+    /// # Panics
+    ///
+    /// panics if the row does not contain the expected column names.
+    pub fn map(&self, row: &Row) -> T {
+        T::from_row_indexed(row, &self.indices)
+    }
+
+    /// Try's to perform the conversion of a single row.
+    ///
+    /// Will return an error if the row does not contain the expected column names.
+    pub fn try_map(&self, row: &Row) -> Result<T, Error> {
+        T::try_from_row_indexed(row, &self.indices)
+    }
+
+    /// Performs the conversion for each row of a batch.
+    ///
+    /// # Panics
+    ///
+    /// panics if any of the rows does not contain the expected column names.
+    pub fn map_rows(&self, rows: &[Row]) -> Vec<T> {
+        rows.iter().map(|row| self.map(row)).collect()
+    }
+
+    /// Try's to perform the conversion for each row of a batch.
+    ///
+    /// Will return the first error encountered, without converting the remaining rows.
+    pub fn try_map_rows(&self, rows: &[Row]) -> Result<Vec<T>, Error> {
+        rows.iter().map(|row| self.try_map(row)).collect()
+    }
+}
+
+impl<T> Clone for RowMapper<T> {
+    fn clone(&self) -> Self {
+        RowMapper {
+            indices: self.indices.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for RowMapper<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RowMapper")
+            .field("indices", &self.indices)
+            .finish()
+    }
+}
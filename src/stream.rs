@@ -0,0 +1,20 @@
+//! Conversion of a stream of rows, such as the [`tokio_postgres::RowStream`] returned by `query_raw`.
+
+use crate::FromRow;
+use futures_util::{stream::Map, Stream, StreamExt};
+use tokio_postgres::{Error, Row};
+
+/// The function that [`RowStreamExt::map_rows`] applies to every row.
+type MapRow<T> = fn(Result<Row, Error>) -> Result<T, Error>;
+
+/// An extension trait for streams of rows, which converts every row as it arrives instead of collecting them first.
+pub trait RowStreamExt: Stream<Item = Result<Row, Error>> + Sized {
+    /// Converts every row of this stream with [`FromRow::try_from_row`].
+    ///
+    /// Errors of the stream itself are passed through, the stream is not stopped at the first error.
+    fn map_rows<T: FromRow>(self) -> Map<Self, MapRow<T>> {
+        self.map(|row| row.and_then(|row| T::try_from_row(&row)))
+    }
+}
+
+impl<S: Stream<Item = Result<Row, Error>>> RowStreamExt for S {}
@@ -1,4 +1,6 @@
-use postgres_from_row::FromRow;
+use postgres_from_row::{
+    FromBorrowedRow, FromGroupedRows, FromRow, FromRowScalar, RenameRule, ToRowParams,
+};
 use tokio_postgres::Row;
 
 #[derive(FromRow)]
@@ -16,11 +18,3206 @@ pub struct User {
     user_id: i32,
 }
 
+#[derive(FromRow, Debug, PartialEq)]
+#[from_row(detailed)]
+pub struct Receipt {
+    receipt_id: i32,
+    #[from_row(rename = "amount_cents")]
+    amount: i64,
+    #[from_row(flatten, prefix = "payer_")]
+    payer: Payer,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+#[from_row(detailed)]
+pub struct Payer {
+    user_id: i32,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+#[from_row(verbose)]
+pub struct Visit {
+    visit_id: i32,
+    page: String,
+    #[from_row(flatten, prefix = "visitor_")]
+    visitor: Bookmark,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Bookmark {
+    bookmark_id: i32,
+    #[from_row(skip)]
+    selected: bool,
+}
+
+#[derive(FromRow)]
+#[from_row(rename_all = "camelCase")]
+#[allow(dead_code)]
+pub struct Author {
+    author_id: i32,
+    display_name: String,
+    #[from_row(rename = "email")]
+    email_address: String,
+}
+
+#[derive(FromRow)]
+#[from_row(prefix = "users.", rename_all = "camelCase")]
+#[allow(dead_code)]
+pub struct Member {
+    // Reads `users.memberId`.
+    member_id: i32,
+    #[from_row(rename = "profiles.bio")]
+    bio: String,
+}
+
+#[derive(FromRow, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[repr(C)]
+#[allow(dead_code)]
+pub struct Note {
+    #[serde(rename = "id")]
+    #[from_row(rename = "note_id")]
+    id: i32,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    body: Option<String>,
+    #[serde(skip)]
+    #[from_row(skip)]
+    dirty: bool,
+}
+
+#[derive(FromRow)]
+#[from_row(column_format = "todo_{}", rename_all = "camelCase")]
+#[allow(dead_code)]
+pub struct Checklist {
+    // Reads `todo_checklistId`.
+    checklist_id: i32,
+    #[from_row(rename = "title")]
+    text: String,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Point(f64, f64);
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Id(i32);
+
+pub async fn single_column(client: &tokio_postgres::Client) -> Result<Id, tokio_postgres::Error> {
+    let row = client
+        .query_one("SELECT max(todo_id) FROM todos", &[])
+        .await?;
+    Id::try_from_row(&row)
+}
+
+#[derive(FromRow)]
+#[from_row(validate_columns)]
+#[allow(dead_code)]
+pub struct Settings {
+    settings_id: i32,
+    #[from_row(default)]
+    theme: Option<String>,
+    #[from_row(default, rename = "page_size")]
+    per_page: i64,
+    #[from_row(default_fn = "default_language")]
+    language: String,
+    #[from_row(optional)]
+    timezone: Option<String>,
+    #[from_row(rename = ["total", "total_count"])]
+    total: i64,
+    #[from_row(default, rename = ["locale", "language_code"])]
+    locale: Option<String>,
+    #[from_row(empty_as_none)]
+    signature: Option<String>,
+    #[from_row(null_to_default, default)]
+    retries: i32,
+}
+
+fn default_language() -> String {
+    "en".to_owned()
+}
+
+#[derive(FromRow)]
+#[from_row(verbose, detailed)]
+#[allow(dead_code)]
+pub struct Measurement {
+    #[from_row(from_fn = "celsius")]
+    temperature: f64,
+    #[from_row(try_from_fn = "non_empty")]
+    label: String,
+    #[from_row(rename = ["unit", "unit_name"])]
+    unit: String,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Reading {
+    #[from_row(from_fn = "Celsius::from_fahrenheit")]
+    temperature: Celsius,
+    #[from_row(from_fn = "<Celsius as From<f64>>::from")]
+    minimum: Celsius,
+    #[from_row(try_from_fn = "Celsius::try_parse")]
+    maximum: Celsius,
+    #[from_row(from_fn = "std::convert::identity::<i64>")]
+    sample: i64,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Forecast {
+    #[from_row(rename = "high_fahrenheit", from_fn = "celsius")]
+    high: f64,
+    #[from_row(rename = "label", try_from_fn = "non_empty")]
+    summary: String,
+}
+
+#[allow(dead_code)]
+pub struct Celsius(f64);
+
+impl Celsius {
+    fn from_fahrenheit(fahrenheit: f64) -> Self {
+        Self(celsius(fahrenheit))
+    }
+
+    fn try_parse(value: String) -> Result<Self, tokio_postgres::Error> {
+        Ok(Self(value.parse().unwrap_or_default()))
+    }
+}
+
+impl From<f64> for Celsius {
+    fn from(value: f64) -> Self {
+        Self(value)
+    }
+}
+
+fn celsius(fahrenheit: f64) -> f64 {
+    (fahrenheit - 32.0) / 1.8
+}
+
+fn non_empty(label: String) -> Result<String, tokio_postgres::Error> {
+    Ok(label)
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Vector {
+    #[from_row(array)]
+    components: [f64; 3],
+    #[from_row(array, default)]
+    labels: [String; 2],
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Assignment {
+    #[from_row(flatten, prefix = "assignee_")]
+    assignee: User,
+    #[from_row(flatten, prefix = "reviewer_")]
+    reviewer: User,
+}
+
+#[derive(FromRow)]
+#[from_row(validate_columns)]
+#[allow(dead_code)]
+pub struct Delivery {
+    delivery_id: i32,
+    // Reads `order_order_id` and `order_customer_user_id`.
+    #[from_row(flatten, prefix = "order_")]
+    order: Order,
+}
+
+#[derive(FromRow)]
+#[from_row(rename_all = "camelCase", verbose)]
+#[allow(dead_code)]
+pub struct Shipment {
+    shipment_id: i32,
+    // Reads `orderId` and `customer_user_id`, since `Order` opts in to the convention but doesn't pass it on to
+    // `customer`.
+    #[from_row(flatten, inherit_rename_all)]
+    order: Order,
+    // Reads `authorId`, `displayName` and `email`, since `Author` renames that field itself.
+    #[from_row(flatten, inherit_rename_all)]
+    author: Author,
+}
+
+#[derive(FromRow)]
+#[from_row(validate_columns, inherit_rename_all)]
+#[allow(dead_code)]
+pub struct Order {
+    order_id: i32,
+    #[from_row(flatten, prefix = "customer_")]
+    customer: User,
+}
+
+#[derive(FromRow, ToRowParams, Debug, PartialEq)]
+pub struct Purchase {
+    purchase_id: i32,
+    #[from_row(default)]
+    note: String,
+    #[from_row(flatten, prefix = "buyer_")]
+    buyer: Buyer,
+}
+
+#[derive(FromRow, ToRowParams, Debug, PartialEq)]
+pub struct Buyer {
+    user_id: i32,
+}
+
+#[derive(FromRow)]
+#[from_row(verbose)]
+#[allow(dead_code)]
+pub struct Ticket {
+    ticket_id: i32,
+    #[from_row(flatten, null_check = "user_id")]
+    assignee: Option<User>,
+    #[from_row(flatten, prefix = "reviewer_", null_check = "user_id")]
+    reviewer: Option<User>,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Location {
+    location_id: i32,
+    #[from_row(from_row_fn = "coordinates")]
+    coordinates: (f64, f64),
+    #[from_row(try_from_row_fn = "try_coordinates")]
+    fallback: (f64, f64),
+}
+
+fn coordinates(row: &Row) -> (f64, f64) {
+    (row.get("lat"), row.get("lon"))
+}
+
+fn try_coordinates(row: &Row) -> Result<(f64, f64), tokio_postgres::Error> {
+    Ok((row.try_get("lat")?, row.try_get("lon")?))
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Person {
+    user_id: i32,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+#[from_row(indexed, verbose, detailed)]
+pub struct Plugin {
+    plugin_id: i32,
+    #[from_row(flatten, prefix = "owner_")]
+    owner: Person,
+    #[from_row(rest)]
+    settings: std::collections::BTreeMap<String, Option<String>>,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+#[from_row(case_insensitive)]
+pub struct Extension {
+    extension_id: i32,
+    #[from_row(rename = ["version", "release"])]
+    version: String,
+    #[from_row(rest)]
+    options: std::collections::BTreeMap<String, String>,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Quota {
+    #[from_row(cast)]
+    used: u64,
+    #[from_row(cast = "i32")]
+    limit: u16,
+    #[from_row(cast)]
+    burst: Option<u32>,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+#[from_row(lenient)]
+pub struct Draft {
+    draft_id: i32,
+    title: String,
+    #[from_row(default_fn = "untitled")]
+    subtitle: String,
+    #[from_row(flatten, prefix = "author_")]
+    author: Person,
+    #[from_row(skip)]
+    saved: bool,
+}
+
+fn untitled() -> String {
+    "untitled".to_owned()
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+#[from_row(lenient)]
+pub struct Classified {
+    listing_id: i32,
+    #[from_row(default_fn = "untitled", null_to_default)]
+    title: String,
+    #[from_row(optional)]
+    summary: Option<String>,
+    #[from_row(default)]
+    views: i64,
+    price: i32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GeoPoint {
+    x: f64,
+    y: f64,
+}
+
+/// Decodes a little endian WKB point, which is all the test needs.
+fn decode_point(wkb: &[u8]) -> Result<GeoPoint, tokio_postgres::Error> {
+    let coordinate =
+        |offset: usize| f64::from_le_bytes(wkb[offset..offset + 8].try_into().unwrap());
+
+    Ok(GeoPoint {
+        x: coordinate(5),
+        y: coordinate(13),
+    })
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Landmark {
+    name: String,
+    #[from_row(wkb = "decode_point")]
+    location: GeoPoint,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Rating {
+    rating_id: i32,
+    #[from_row(get_or_default)]
+    stars: i32,
+}
+
+#[derive(FromRow)]
+#[from_row(case_insensitive)]
+#[allow(dead_code)]
+pub struct Street {
+    street_id: i32,
+    #[from_row(default)]
+    name: String,
+    #[from_row(default, rename = "größe")]
+    size: i32,
+}
+
+#[derive(FromRow)]
+#[from_row(indexed)]
+#[allow(dead_code)]
+pub struct Event {
+    event_id: i64,
+    #[from_row(default)]
+    payload: Option<String>,
+    #[from_row(flatten, prefix = "author_")]
+    author: User,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+#[from_row(panic_via_try, indexed)]
+pub struct Gauge {
+    sensor_id: i32,
+    value: f64,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Article {
+    #[from_row(with = "trimmed")]
+    title: String,
+    #[from_row(with = "trimmed", default)]
+    subtitle: String,
+}
+
+mod trimmed {
+    use tokio_postgres::{Error, Row};
+
+    pub fn from_row_value(row: &Row, column: &str) -> String {
+        row.get::<_, String>(column).trim().to_owned()
+    }
+
+    pub fn try_from_row_value(row: &Row, column: &str) -> Result<String, Error> {
+        Ok(row.try_get::<_, String>(column)?.trim().to_owned())
+    }
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Statistics {
+    #[from_row(column = 0)]
+    total: i64,
+    #[from_row(column = 1)]
+    average: f64,
+}
+
+#[derive(FromRow)]
+#[from_row(tag = "kind")]
+pub enum Account {
+    #[from_row(rename = "user")]
+    User {
+        user_id: i32,
+        #[from_row(rename = "name")]
+        username: String,
+    },
+    Guest,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Profile {
+    #[from_row(wrap = "arc")]
+    name: std::sync::Arc<str>,
+    #[from_row(wrap = "box")]
+    avatar: Box<[u8]>,
+    #[from_row(wrap = "rc")]
+    bio: std::rc::Rc<String>,
+    #[from_row(flatten, wrap = "box")]
+    owner: Box<User>,
+    #[from_row(flatten, prefix = "editor_")]
+    editor: Box<User>,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct CacheEntry {
+    key: Box<str>,
+    value: Option<Box<str>>,
+}
+
+#[derive(FromRow)]
+#[from_row(build_with = "Range::new")]
+#[allow(dead_code)]
+pub struct Range {
+    start: i32,
+    end: i32,
+}
+
+impl Range {
+    fn new(start: i32, end: i32) -> Self {
+        assert!(start <= end, "invalid range");
+        Self { start, end }
+    }
+}
+
+#[derive(FromRow)]
+#[from_row(try_build_with = "Interval::new", indexed, verbose, detailed)]
+#[allow(dead_code)]
+pub struct Interval {
+    lower: i32,
+    upper: i32,
+}
+
+impl Interval {
+    fn new(lower: i32, upper: i32) -> Result<Self, tokio_postgres::Error> {
+        Ok(Self {
+            lower: lower.min(upper),
+            upper: upper.max(lower),
+        })
+    }
+}
+
+#[derive(FromRow)]
+pub struct Empty {}
+
+#[derive(FromRow)]
+pub struct Unit;
+
 #[allow(dead_code)]
 fn from_row(row: &Row) {
     let _ = Todo::from_row(row);
+    let _ = Visit::try_from_row_verbose(row).unwrap();
+    let _ = Receipt::try_from_row_detailed(row).unwrap();
     let _ = Todo::try_from_row(row).unwrap();
 
     let _ = User::from_row(row);
     let _ = Todo::try_from_row(row).unwrap();
+    let _ = User::from_row_opt(row);
+    let _ = Todo::try_from_row_opt(row).unwrap();
+
+    let _ = Author::from_row(row);
+    let _ = Author::try_from_row(row).unwrap();
+
+    let _ = Member::from_row(row);
+    let _ = Member::try_from_row(row).unwrap();
+
+    let _ = Point::from_row(row);
+    let _ = Point::try_from_row(row).unwrap();
+
+    let _ = Settings::from_row(row);
+    let _ = Settings::try_from_row(row).unwrap();
+
+    let _ = Measurement::from_row(row);
+    let _ = Measurement::try_from_row(row).unwrap();
+    let _ = Measurement::try_from_row_verbose(row).unwrap();
+    let _ = Measurement::try_from_row_detailed(row).unwrap();
+
+    let _ = Reading::from_row(row);
+    let _ = Reading::try_from_row(row).unwrap();
+
+    let _ = Vector::from_row(row);
+    let _ = Vector::try_from_row(row).unwrap();
+
+    let _ = Assignment::from_row(row);
+    let _ = Assignment::try_from_row(row).unwrap();
+
+    let _ = User::from_row_with_prefix(row, "assignee_");
+    let _ = User::try_from_row_with_prefix(row, "reviewer_").unwrap();
+
+    let _ = Delivery::from_row(row);
+    let _ = Delivery::try_from_row(row).unwrap();
+    let _ = Order::from_row_with_prefix(row, "order_");
+
+    let _ = Ticket::from_row(row);
+    let _ = Ticket::try_from_row(row).unwrap();
+    let _ = Ticket::try_from_row_verbose(row).unwrap();
+
+    let _ = Location::from_row(row);
+    let _ = Location::try_from_row(row).unwrap();
+
+    let _ = Street::from_row(row);
+    let _ = Street::try_from_row(row).unwrap();
+
+    let _ = Article::from_row(row);
+    let _ = Article::try_from_row(row).unwrap();
+
+    let _ = Statistics::from_row(row);
+    let _ = Statistics::try_from_row(row).unwrap();
+
+    let _ = Profile::from_row(row);
+    let _ = Profile::try_from_row(row).unwrap();
+
+    let _ = Range::from_row(row);
+    let _ = Range::try_from_row(row).unwrap();
+
+    let _ = Interval::from_row(row);
+    let _ = Interval::try_from_row(row).unwrap();
+    let _ = Interval::try_from_row_verbose(row).unwrap();
+    let _ = Interval::try_from_row_detailed(row).unwrap();
+
+    let _ = Account::from_row(row);
+    let _ = Account::try_from_row(row).unwrap();
+
+    let _: (i32,) = FromRow::from_row(row);
+    let _: (i32, String, Option<f64>) = FromRow::try_from_row(row).unwrap();
+
+    let Empty {} = Empty::from_row(row);
+    let Empty {} = Empty::try_from_row(row).unwrap();
+
+    let Unit = Unit::from_row(row);
+    let Unit = Unit::try_from_row(row).unwrap();
+}
+
+mod cols {
+    pub const INVOICE_ID: &str = "invoice_id";
+    pub const TOTAL: &str = "total_cents";
+}
+
+#[derive(FromRow, ToRowParams)]
+#[from_row(indexed, verbose, detailed, validate_columns)]
+#[allow(dead_code)]
+pub struct Invoice {
+    #[from_row(rename_const = "cols::INVOICE_ID")]
+    invoice_id: i32,
+    #[from_row(rename_const = "crate::cols::TOTAL", default)]
+    total: i64,
+}
+
+#[allow(dead_code)]
+fn rename_const(row: &Row) {
+    let _ = Invoice::from_row(row);
+    let _ = Invoice::try_from_row(row).unwrap();
+}
+
+#[derive(FromRow)]
+#[from_row(transparent, rename = "email", verbose, detailed, validate_columns)]
+#[allow(dead_code)]
+pub struct Email {
+    value: String,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Contact {
+    user_id: i32,
+    #[from_row(flatten, prefix = "work_")]
+    email: Email,
+}
+
+#[allow(dead_code)]
+fn transparent(row: &Row) {
+    let _ = Email::from_row(row);
+    let _ = Contact::try_from_row(row).unwrap();
+}
+
+#[test]
+fn columns() {
+    assert_eq!(User::COLUMNS, ["user_id"]);
+    assert_eq!(Member::COLUMNS, ["users.memberId", "profiles.bio"]);
+    assert_eq!(Checklist::COLUMNS, ["todo_checklistId", "title"]);
+    assert_eq!(Note::COLUMNS, ["note_id", "body"]);
+    assert_eq!(
+        Delivery::COLUMNS,
+        ["delivery_id", "order_order_id", "order_customer_user_id"]
+    );
+    assert_eq!(Account::COLUMNS, ["kind", "user_id", "name"]);
+    assert_eq!(Invoice::COLUMNS, ["invoice_id", "total_cents"]);
+    assert_eq!(Forecast::COLUMNS, ["high_fahrenheit", "label"]);
+    assert_eq!(Contact::COLUMNS, ["user_id", "work_email"]);
+    assert_eq!(
+        Profile::COLUMNS,
+        ["name", "avatar", "bio", "user_id", "editor_user_id"]
+    );
+}
+
+#[test]
+fn rename_rule() {
+    assert_eq!(RenameRule::Camel.apply("user_id"), "userId");
+    assert_eq!(RenameRule::ScreamingKebab.apply("user_id"), "USER-ID");
+}
+
+macro_rules! renamed {
+    ($($name:ident => $rule:literal, $variant:ident;)*) => {
+        $(
+            #[derive(FromRow)]
+            #[from_row(rename_all = $rule)]
+            #[allow(dead_code, non_snake_case)]
+            pub struct $name {
+                id: i32,
+                user_id: i32,
+                http2_url: i32,
+                trailing_: i32,
+                double__underscore: i32,
+                _leading: i32,
+            }
+        )*
+
+        /// The rules of the derive and of [`RenameRule`] are implemented separately, so both have to agree.
+        #[test]
+        fn rename_rule_matches_derive() {
+            let fields = [
+                "id",
+                "user_id",
+                "http2_url",
+                "trailing_",
+                "double__underscore",
+                "_leading",
+            ];
+
+            $(
+                let expected = fields.map(|field| RenameRule::$variant.apply(field));
+                assert_eq!($name::COLUMNS, expected, $rule);
+            )*
+        }
+    };
+}
+
+renamed! {
+    RenamedLower => "lowercase", Lower;
+    RenamedUpper => "UPPERCASE", Upper;
+    RenamedPascal => "PascalCase", Pascal;
+    RenamedCamel => "camelCase", Camel;
+    RenamedSnake => "snake_case", Snake;
+    RenamedScreamingSnake => "SCREAMING_SNAKE_CASE", ScreamingSnake;
+    RenamedKebab => "kebab-case", Kebab;
+    RenamedScreamingKebab => "SCREAMING-KEBAB-CASE", ScreamingKebab;
+}
+
+#[test]
+fn param_columns() {
+    assert_eq!(
+        Purchase::PARAM_COLUMNS,
+        ["purchase_id", "note", "buyer_user_id"]
+    );
+    assert_eq!(Invoice::PARAM_COLUMNS, ["invoice_id", "total_cents"]);
+}
+
+#[allow(dead_code)]
+fn to_row_params(purchase: &Purchase) {
+    let _ = purchase.params();
+}
+
+#[allow(dead_code)]
+fn from_rows(rows: &[Row]) {
+    let _: Vec<Todo> = Todo::from_rows(rows);
+    let _: Vec<Todo> = Todo::try_from_rows(rows).unwrap();
+
+    let _: Vec<Event> = Event::from_rows(rows);
+    let _: Vec<Event> = Event::try_from_rows(rows).unwrap();
+
+    let indices = Event::resolve_indices(rows[0].columns());
+    let _ = Event::from_row_indexed(&rows[0], &indices);
+    let _ = Event::try_from_row_indexed(&rows[0], &indices).unwrap();
+
+    let _: Vec<Gauge> = Gauge::from_rows(rows);
+}
+
+#[allow(dead_code)]
+fn row_to_map(row: &Row) {
+    let _: std::collections::HashMap<String, i64> = postgres_from_row::row_to_map(row);
+    let _ = postgres_from_row::try_row_to_map::<Option<String>>(row).unwrap();
+}
+
+#[cfg(feature = "json")]
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Preferences {
+    #[from_row(json)]
+    limits: std::collections::HashMap<String, i32>,
+    #[from_row(json, default)]
+    tags: Vec<String>,
+}
+
+#[cfg(feature = "json")]
+#[allow(dead_code)]
+fn row_to_json(row: &Row) {
+    let _ = postgres_from_row::row_to_json(row);
+    let _ = postgres_from_row::try_row_to_json(row).unwrap();
+
+    let _ = Preferences::from_row(row);
+    let _ = Preferences::try_from_row(row).unwrap();
+}
+
+#[cfg(feature = "serde")]
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Listing {
+    #[from_row(json_text)]
+    tags: Vec<String>,
+    #[from_row(deserialize_with = "split_authors")]
+    authors: Vec<String>,
+}
+
+#[cfg(feature = "uuid")]
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Device {
+    #[from_row(uuid_text)]
+    device_id: uuid::Uuid,
+    #[from_row(uuid_text)]
+    parent_id: Option<uuid::Uuid>,
+}
+
+#[cfg(feature = "rust_decimal")]
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Payment {
+    #[from_row(decimal)]
+    total: rust_decimal::Decimal,
+    #[from_row(decimal)]
+    fee: rust_decimal::Decimal,
+    #[from_row(decimal)]
+    discount: Option<rust_decimal::Decimal>,
+    #[from_row(decimal)]
+    imported: rust_decimal::Decimal,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Lease {
+    lease_id: i32,
+    #[from_row(interval)]
+    duration: std::time::Duration,
+    #[from_row(interval)]
+    grace: Option<std::time::Duration>,
+}
+
+#[cfg(feature = "serde")]
+fn split_authors(text: &str) -> Result<Vec<String>, tokio_postgres::Error> {
+    Ok(text.split(',').map(str::to_owned).collect())
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Inbox {
+    user_id: i32,
+    #[from_row(nonzero_as_bool, rename = "unread_count")]
+    has_unread: bool,
+}
+
+#[allow(dead_code)]
+fn nonzero_as_bool(row: &Row) {
+    let _ = Inbox::from_row(row);
+    let _ = Inbox::try_from_row(row).unwrap();
+}
+
+/// A type with the same api as one generated by `bitflags!`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Permissions(i64);
+
+impl Permissions {
+    pub fn from_bits_truncate(bits: i64) -> Self {
+        Permissions(bits & 0b11)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Visibility(i32);
+
+impl Visibility {
+    pub fn from_bits_truncate(bits: i32) -> Self {
+        Visibility(bits & 0b1)
+    }
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Role {
+    role_id: i32,
+    #[from_row(bit_flags)]
+    permissions: Permissions,
+    #[from_row(bit_flags = "i32", default_fn = "hidden")]
+    visibility: Visibility,
+}
+
+fn hidden() -> Visibility {
+    Visibility(0)
+}
+
+#[allow(dead_code)]
+fn bit_flags(row: &Row) {
+    let _ = Role::from_row(row);
+    let _ = Role::try_from_row(row).unwrap();
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Product {
+    product_id: i32,
+    #[from_row(hstore)]
+    attributes: std::collections::HashMap<String, Option<String>>,
+    #[from_row(hstore, default)]
+    labels: std::collections::HashMap<String, Option<String>, std::hash::RandomState>,
+}
+
+#[allow(dead_code)]
+fn hstore(row: &Row) {
+    let _ = Product::from_row(row);
+    let _ = Product::try_from_row(row).unwrap();
+}
+
+#[test]
+fn hstore_column() {
+    use tokio_postgres::types::{FromSql, Kind, Type};
+
+    let ty = Type::new("hstore".to_owned(), 0, Kind::Simple, "public".to_owned());
+
+    let mut raw = 2i32.to_be_bytes().to_vec();
+    raw.extend(5i32.to_be_bytes());
+    raw.extend(b"color");
+    raw.extend(3i32.to_be_bytes());
+    raw.extend(b"red");
+    raw.extend(4i32.to_be_bytes());
+    raw.extend(b"size");
+    raw.extend((-1i32).to_be_bytes());
+
+    let attributes =
+        std::collections::HashMap::<String, Option<String>>::from_sql(&ty, &raw).unwrap();
+
+    assert!(<std::collections::HashMap<String, Option<String>> as FromSql>::accepts(&ty));
+    assert_eq!(attributes["color"].as_deref(), Some("red"));
+    assert_eq!(attributes["size"], None);
+}
+
+#[cfg(feature = "chrono")]
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Session {
+    session_id: i32,
+    #[from_row(utc)]
+    created_at: chrono::DateTime<chrono::Utc>,
+    #[from_row(utc, default)]
+    expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg(feature = "chrono")]
+#[allow(dead_code)]
+fn utc(row: &Row) {
+    let _ = Session::from_row(row);
+    let _ = Session::try_from_row(row).unwrap();
+}
+
+#[cfg(feature = "sqlx")]
+#[derive(FromRow)]
+#[from_row(sqlx, rename_all = "camelCase", lifetime = "'row")]
+#[allow(dead_code)]
+pub struct Customer {
+    customer_id: i32,
+    #[from_row(wrap = "arc")]
+    display_name: std::sync::Arc<str>,
+    #[from_row(default)]
+    email: String,
+    #[from_row(empty_as_none)]
+    phone: Option<String>,
+    #[from_row(null_to_default)]
+    notes: String,
+    #[from_row(skip)]
+    selected: bool,
+}
+
+#[cfg(feature = "sqlx")]
+#[allow(dead_code)]
+fn from_sqlx_row(row: &postgres_from_row::sqlx::postgres::PgRow) {
+    use postgres_from_row::sqlx::FromRow as SqlxFromRow;
+
+    let _ = <Customer as SqlxFromRow<_>>::from_row(row).unwrap();
+}
+
+#[cfg(feature = "stream")]
+pub async fn map_rows(
+    client: &tokio_postgres::Client,
+) -> Result<
+    impl futures_util::Stream<Item = Result<Todo, tokio_postgres::Error>>,
+    tokio_postgres::Error,
+> {
+    use postgres_from_row::RowStreamExt;
+
+    let rows = client
+        .query_raw("SELECT todo_id, text, user_id FROM todos", &[] as &[i32])
+        .await?;
+    Ok(rows.map_rows::<Todo>())
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Slug<'a> {
+    slug: &'a str,
+    #[from_row(default)]
+    data: Option<&'a [u8]>,
+    #[from_row(flatten, prefix = "author_")]
+    author: Author,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Attachment<'a> {
+    file_name: std::borrow::Cow<'a, str>,
+    contents: std::borrow::Cow<'a, [u8]>,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Page<'a> {
+    page_id: i32,
+    #[from_row(flatten)]
+    slug: Slug<'a>,
+}
+
+pub fn borrowed_from_row(row: &tokio_postgres::Row) -> &str {
+    let page = Page::from_row(row);
+    page.slug.slug
+}
+
+pub fn borrowed_from_rows(rows: &[tokio_postgres::Row]) -> Vec<Slug<'_>> {
+    Slug::from_rows(rows)
+}
+
+pub fn borrowed_cow(row: &tokio_postgres::Row) -> std::borrow::Cow<'_, [u8]> {
+    Attachment::from_row(row).contents
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+#[allow(dead_code)]
+pub struct Wrapper<T: Clone> {
+    value: T,
+}
+
+#[derive(FromRow, ToRowParams, Debug, PartialEq)]
+#[allow(dead_code)]
+pub struct Pair<T>
+where
+    T: Send,
+{
+    first: T,
+    second: T,
+}
+
+pub fn generic_from_row(row: &tokio_postgres::Row) -> (Wrapper<String>, Pair<i32>) {
+    (Wrapper::from_row(row), Pair::from_row(row))
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Revision<T: Clone, U: Send> {
+    revision: i32,
+    #[from_row(flatten, prefix = "old_")]
+    old: Wrapper<T>,
+    #[from_row(flatten, prefix = "new_")]
+    new: Pair<U>,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct RawReviewer {
+    reviewer_id: i32,
+    reviewer_name: Option<String>,
+}
+
+#[allow(dead_code)]
+pub struct Reviewer {
+    id: i32,
+    name: String,
+}
+
+impl TryFrom<RawReviewer> for Reviewer {
+    type Error = tokio_postgres::Error;
+
+    fn try_from(raw: RawReviewer) -> Result<Self, Self::Error> {
+        Ok(Reviewer {
+            id: raw.reviewer_id,
+            name: raw.reviewer_name.unwrap_or_default(),
+        })
+    }
+}
+
+impl From<RawReviewer> for Author {
+    fn from(raw: RawReviewer) -> Self {
+        Author {
+            author_id: raw.reviewer_id,
+            display_name: raw.reviewer_name.unwrap_or_default(),
+            email_address: String::new(),
+        }
+    }
+}
+
+#[derive(FromRow)]
+#[from_row(verbose, detailed)]
+#[allow(dead_code)]
+pub struct Review {
+    review_id: i32,
+    #[from_row(flatten, try_from = "RawReviewer")]
+    reviewer: Reviewer,
+    #[from_row(flatten, prefix = "second_", from = "RawReviewer")]
+    second_reviewer: Author,
+}
+
+pub fn detailed(row: &Row) -> Result<Review, tokio_postgres::Error> {
+    Review::try_from_row_detailed(row).map_err(postgres_from_row::FromRowError::into_inner)
+}
+
+pub fn validate_columns(
+    statement: &tokio_postgres::Statement,
+) -> Result<(), postgres_from_row::ColumnError> {
+    Settings::validate_columns(statement.columns())?;
+    Delivery::validate_columns(statement.columns())
+}
+
+mod reexport {
+    pub use postgres_from_row as db;
+}
+
+#[derive(reexport::db::FromRow, reexport::db::ToRowParams)]
+#[from_row(crate = "reexport::db", verbose)]
+#[allow(dead_code)]
+pub struct Label {
+    label_id: i32,
+    #[from_row(default)]
+    color: String,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+#[from_row(composite, lifetime = "'row")]
+pub struct Address {
+    street: String,
+    #[from_row(rename = "zip_code")]
+    zip: Option<String>,
+    #[from_row(default)]
+    country: String,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Warehouse {
+    warehouse_id: i32,
+    address: Address,
+    #[from_row(aggregate)]
+    docks: Vec<Address>,
+}
+
+#[test]
+fn composite() {
+    use tokio_postgres::types::{Field, FromSql, Kind, Type};
+
+    let ty = Type::new(
+        "address".to_owned(),
+        0,
+        Kind::Composite(vec![
+            Field::new("zip_code".to_owned(), Type::TEXT),
+            Field::new("street".to_owned(), Type::TEXT),
+        ]),
+        "public".to_owned(),
+    );
+
+    let mut raw = 2i32.to_be_bytes().to_vec();
+    raw.extend(Type::TEXT.oid().to_be_bytes());
+    raw.extend((-1i32).to_be_bytes());
+    raw.extend(Type::TEXT.oid().to_be_bytes());
+    raw.extend(4i32.to_be_bytes());
+    raw.extend(b"Main");
+
+    assert!(Address::accepts(&ty));
+    assert!(!Address::accepts(&Type::TEXT));
+
+    let array = Type::new(
+        "_address".to_owned(),
+        0,
+        Kind::Array(ty.clone()),
+        "public".to_owned(),
+    );
+    assert!(Vec::<Address>::accepts(&array));
+    assert_eq!(
+        Address::from_sql(&ty, &raw).unwrap(),
+        Address {
+            street: "Main".to_owned(),
+            zip: None,
+            country: String::new(),
+        }
+    );
+}
+
+mod billing {
+    use postgres_from_row::FromRow;
+
+    #[derive(FromRow)]
+    pub struct Balance {
+        cents: i64,
+        currency: String,
+    }
+
+    impl Balance {
+        pub fn cents(&self) -> i64 {
+            self.cents
+        }
+
+        pub fn currency(&self) -> &str {
+            &self.currency
+        }
+    }
+
+    #[derive(FromRow)]
+    #[from_row(build_with = "Refund::new")]
+    pub struct Refund {
+        cents: i64,
+        reason: Option<String>,
+    }
+
+    impl Refund {
+        fn new(cents: i64, reason: Option<String>) -> Self {
+            assert!(cents > 0, "a refund must be positive");
+            Self { cents, reason }
+        }
+
+        pub fn cents(&self) -> i64 {
+            self.cents
+        }
+
+        pub fn reason(&self) -> Option<&str> {
+            self.reason.as_deref()
+        }
+    }
+}
+
+mod geo {
+    pub struct Coordinates {
+        pub lat: f64,
+        pub lng: f64,
+    }
+}
+
+#[derive(FromRow)]
+#[from_row(remote = "geo::Coordinates")]
+#[allow(dead_code)]
+struct CoordinatesDef {
+    #[from_row(rename = "latitude")]
+    lat: f64,
+    #[from_row(rename = "longitude")]
+    lng: f64,
+}
+
+pub fn remote(row: &Row) -> geo::Coordinates {
+    geo::Coordinates::from_row(row)
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+#[from_row(indexed)]
+pub struct Shuffled {
+    todo_id: i32,
+    #[from_row(rename = "title")]
+    text: String,
+    #[from_row(default)]
+    priority: i32,
+    #[from_row(flatten, prefix = "author_")]
+    author: ShuffledAuthor,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct ShuffledAuthor {
+    user_id: i32,
+    username: String,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+#[from_row(indexed)]
+pub struct Waypoint {
+    waypoint_id: i32,
+    #[from_row(rename = ["label", "name"])]
+    label: String,
+    #[from_row(default)]
+    altitude: i32,
+    #[from_row(flatten, prefix = "author_")]
+    author: ShuffledAuthor,
+}
+
+#[derive(FromGroupedRows, Debug, PartialEq)]
+pub struct Blog {
+    #[from_row(group_key)]
+    user_id: i32,
+    username: String,
+    #[from_row(children, prefix = "post_", null_check = "post_id")]
+    posts: Vec<Post>,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Post {
+    post_id: i32,
+    title: String,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Buffered {
+    todo_id: i32,
+    text: String,
+    #[from_row(skip)]
+    reused: u32,
+}
+
+pub trait Schema {
+    type Id;
+    type Name;
+    type Owner;
+}
+
+pub struct Wallets;
+
+impl Schema for Wallets {
+    type Id = i32;
+    type Name = String;
+    type Owner = User;
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Wallet {
+    wallet_id: <Wallets as Schema>::Id,
+    #[from_row(optional)]
+    name: Option<<Wallets as Schema>::Name>,
+    #[from_row(from = "<Wallets as Schema>::Id")]
+    balance: i64,
+    #[from_row(wrap = "arc")]
+    label: std::sync::Arc<<Wallets as Schema>::Name>,
+    #[from_row(default)]
+    limits: ::std::collections::HashMap<
+        ::std::string::String,
+        ::std::option::Option<::std::string::String>,
+    >,
+    #[from_row(flatten, prefix = "owner_")]
+    owner: <Wallets as Schema>::Owner,
+}
+
+#[derive(FromRow)]
+#[from_row(indexed, verbose, detailed, validate_columns)]
+#[cfg_attr(feature = "sqlx", from_row(sqlx))]
+#[allow(dead_code)]
+pub struct SchemaWallet<S: Schema> {
+    wallet_id: S::Id,
+    name: <S as Schema>::Name,
+    #[from_row(null_to_default)]
+    nickname: S::Name,
+}
+
+#[allow(dead_code)]
+fn associated_types(row: &Row) {
+    let _ = Wallet::from_row(row);
+    let _ = Wallet::try_from_row(row).unwrap();
+
+    let _ = SchemaWallet::<Wallets>::from_row(row);
+    let _ = SchemaWallet::<Wallets>::try_from_row(row).unwrap();
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Active,
+    Inactive,
+}
+
+impl std::str::FromStr for Status {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "active" => Ok(Status::Active),
+            "inactive" => Ok(Status::Inactive),
+            _ => Err(format!("unknown status `{text}`")),
+        }
+    }
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Subscription {
+    #[from_row(enum_text)]
+    status: Status,
+    #[from_row(enum_text, optional)]
+    previous: Option<Status>,
+}
+
+/// Connects to the database in `DATABASE_URL`. Tests that need a database are ignored by default, run them with
+/// `DATABASE_URL=... cargo test -- --ignored`.
+async fn connect() -> tokio_postgres::Client {
+    let url = std::env::var("DATABASE_URL")
+        .expect("`DATABASE_URL` must be set to run the tests that need a database");
+    let (client, connection) = tokio_postgres::connect(&url, tokio_postgres::NoTls)
+        .await
+        .unwrap();
+
+    tokio::spawn(connection);
+    client
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn column_order() {
+    let client = connect().await;
+
+    let expected = Shuffled {
+        todo_id: 1,
+        text: "write tests".to_owned(),
+        priority: 2,
+        author: ShuffledAuthor {
+            user_id: 3,
+            username: "remo".to_owned(),
+        },
+    };
+
+    for query in [
+        "SELECT 1 AS todo_id, 'write tests' AS title, 2 AS priority, 3 AS author_user_id, 'remo' AS author_username",
+        "SELECT 'remo' AS author_username, 2 AS priority, 3 AS author_user_id, 'write tests' AS title, 1 AS todo_id",
+        "SELECT 3 AS author_user_id, 1 AS todo_id, 'remo' AS author_username, 2 AS priority, 'write tests' AS title",
+    ] {
+        let rows = client.query(query, &[]).await.unwrap();
+
+        assert_eq!(Shuffled::from_row(&rows[0]), expected);
+        assert_eq!(Shuffled::try_from_row(&rows[0]).unwrap(), expected);
+        assert_eq!(Shuffled::from_rows(&rows), std::slice::from_ref(&expected));
+        assert_eq!(Shuffled::try_from_rows(&rows).unwrap(), std::slice::from_ref(&expected));
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn grouped_rows() {
+    let client = connect().await;
+
+    let rows = client
+        .query(
+            "SELECT * FROM (VALUES (1, 'remo', 10, 'first'), (1, 'remo', 11, 'second'), (2, 'tom', NULL, NULL)) \
+                AS blogs (user_id, username, post_post_id, post_title)",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = vec![
+        Blog {
+            user_id: 1,
+            username: "remo".to_owned(),
+            posts: vec![
+                Post {
+                    post_id: 10,
+                    title: "first".to_owned(),
+                },
+                Post {
+                    post_id: 11,
+                    title: "second".to_owned(),
+                },
+            ],
+        },
+        Blog {
+            user_id: 2,
+            username: "tom".to_owned(),
+            posts: Vec::new(),
+        },
+    ];
+
+    assert_eq!(Blog::from_grouped_rows(&rows), expected);
+    assert_eq!(Blog::try_from_grouped_rows(&rows).unwrap(), expected);
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn json_text() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT '[\"rust\", \"postgres\"]'::TEXT AS tags, 'remo,tom' AS authors",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Listing {
+        tags: vec!["rust".to_owned(), "postgres".to_owned()],
+        authors: vec!["remo".to_owned(), "tom".to_owned()],
+    };
+
+    assert_eq!(Listing::from_row(&row), expected);
+    assert_eq!(Listing::try_from_row(&row).unwrap(), expected);
+
+    let row = client
+        .query_one("SELECT 'rust' AS tags, 'remo' AS authors", &[])
+        .await
+        .unwrap();
+
+    assert!(Listing::try_from_row(&row).is_err());
+}
+
+#[cfg(feature = "uuid")]
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn uuid_text() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT '67e55044-10b1-426f-9247-bb680e5fe0c8' AS device_id, NULL::TEXT AS parent_id",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Device {
+        device_id: uuid::Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8),
+        parent_id: None,
+    };
+
+    assert_eq!(Device::from_row(&row), expected);
+    assert_eq!(Device::try_from_row(&row).unwrap(), expected);
+
+    let row = client
+        .query_one("SELECT 'device' AS device_id, NULL::TEXT AS parent_id", &[])
+        .await
+        .unwrap();
+
+    assert!(Device::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn update_in_place() {
+    let client = connect().await;
+
+    let rows = client
+        .query(
+            "SELECT * FROM (VALUES (1, 'first'), (2, 'second')) AS todos (todo_id, text)",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let mut buffer = Buffered {
+        todo_id: 0,
+        text: String::new(),
+        reused: 0,
+    };
+
+    for row in &rows {
+        buffer.update_from_row(row);
+        buffer.reused += 1;
+    }
+
+    assert_eq!(
+        buffer,
+        Buffered {
+            todo_id: 2,
+            text: "second".to_owned(),
+            reused: 2,
+        }
+    );
+
+    let row = client.query_one("SELECT 3 AS todo_id", &[]).await.unwrap();
+
+    assert!(buffer.try_update_from_row(&row).is_err());
+    assert_eq!(buffer.todo_id, 2);
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn rename_with_from_fn() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 212.0::FLOAT8 AS high_fahrenheit, 0.0::FLOAT8 AS high, 'sunny' AS label, '' AS summary",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Forecast {
+        high: 100.0,
+        summary: "sunny".to_owned(),
+    };
+
+    assert_eq!(Forecast::from_row(&row), expected);
+    assert_eq!(Forecast::try_from_row(&row).unwrap(), expected);
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn enum_text() {
+    let client = connect().await;
+
+    client
+        .batch_execute("CREATE TYPE pg_temp.status AS ENUM ('active', 'inactive')")
+        .await
+        .unwrap();
+
+    let row = client
+        .query_one("SELECT 'inactive'::pg_temp.status AS status", &[])
+        .await
+        .unwrap();
+
+    let expected = Subscription {
+        status: Status::Inactive,
+        previous: None,
+    };
+
+    assert_eq!(Subscription::from_row(&row), expected);
+    assert_eq!(Subscription::try_from_row(&row).unwrap(), expected);
+
+    let row = client
+        .query_one("SELECT 'unknown' AS status", &[])
+        .await
+        .unwrap();
+
+    assert!(Subscription::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn panic_via_try() {
+    let client = connect().await;
+
+    let row = client
+        .query_one("SELECT 1 AS sensor_id, 2.5::float8 AS value", &[])
+        .await
+        .unwrap();
+
+    let expected = Gauge {
+        sensor_id: 1,
+        value: 2.5,
+    };
+
+    assert_eq!(Gauge::from_row(&row), expected);
+
+    let indices = Gauge::resolve_indices(row.columns());
+    assert_eq!(Gauge::from_row_indexed(&row, &indices), expected);
+
+    let mut gauge = Gauge {
+        sensor_id: 0,
+        value: 0.0,
+    };
+    gauge.update_from_row(&row);
+    assert_eq!(gauge, expected);
+
+    let row = client
+        .query_one("SELECT 1 AS sensor_id", &[])
+        .await
+        .unwrap();
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| Gauge::from_row(&row)))
+        .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+
+    assert!(message.starts_with("from_row failed for Gauge"));
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn get_or_default() {
+    let client = connect().await;
+
+    let row = client
+        .query_one("SELECT 1 AS rating_id, 'five' AS stars", &[])
+        .await
+        .unwrap();
+
+    let expected = Rating {
+        rating_id: 1,
+        stars: 0,
+    };
+
+    assert_eq!(Rating::from_row(&row), expected);
+    assert!(Rating::try_from_row(&row).is_err());
+
+    let row = client
+        .query_one("SELECT 1 AS rating_id", &[])
+        .await
+        .unwrap();
+
+    assert_eq!(Rating::from_row(&row), expected);
+    assert!(Rating::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn private_fields() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 250::INT8 AS cents, 'EUR' AS currency, NULL::TEXT AS reason",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let balance = billing::Balance::from_row(&row);
+    assert_eq!(balance.cents(), 250);
+    assert_eq!(balance.currency(), "EUR");
+
+    let refund = billing::Refund::try_from_row(&row).unwrap();
+    assert_eq!(refund.cents(), 250);
+    assert_eq!(refund.reason(), None);
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn cast() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 12::INT8 AS used, 100 AS limit, NULL::INT8 AS burst",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Quota {
+        used: 12,
+        limit: 100,
+        burst: None,
+    };
+
+    assert_eq!(Quota::from_row(&row), expected);
+    assert_eq!(Quota::try_from_row(&row).unwrap(), expected);
+
+    let row = client
+        .query_one(
+            "SELECT -1::INT8 AS used, 100000 AS limit, 5::INT8 AS burst",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert!(Quota::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn rest() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS plugin_id, 2 AS owner_user_id, 'dark' AS theme, NULL::TEXT AS locale",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Plugin {
+        plugin_id: 1,
+        owner: Person { user_id: 2 },
+        settings: [
+            ("theme".to_owned(), Some("dark".to_owned())),
+            ("locale".to_owned(), None),
+        ]
+        .into_iter()
+        .collect(),
+    };
+
+    assert_eq!(Plugin::from_row(&row), expected);
+    assert_eq!(Plugin::try_from_row(&row).unwrap(), expected);
+
+    let indices = Plugin::resolve_indices(row.columns());
+    assert_eq!(Plugin::from_row_indexed(&row, &indices), expected);
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS plugin_id, 2 AS owner_user_id, 3 AS retries",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert!(Plugin::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn rest_known_columns() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            r#"SELECT 1 AS "Extension_Id", '2.0' AS release, 'on' AS mode"#,
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Extension {
+        extension_id: 1,
+        version: "2.0".to_owned(),
+        options: [("mode".to_owned(), "on".to_owned())].into_iter().collect(),
+    };
+
+    assert_eq!(Extension::from_row(&row), expected);
+    assert_eq!(Extension::try_from_row(&row).unwrap(), expected);
+}
+
+#[cfg(feature = "rust_decimal")]
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn decimal() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 12.50::NUMERIC AS total, 1.25::MONEY AS fee, NULL::NUMERIC AS discount, '3.75' AS imported",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Payment {
+        total: rust_decimal::Decimal::new(1250, 2),
+        fee: rust_decimal::Decimal::new(125, 2),
+        discount: None,
+        imported: rust_decimal::Decimal::new(375, 2),
+    };
+
+    assert_eq!(Payment::from_row(&row), expected);
+    assert_eq!(Payment::try_from_row(&row).unwrap(), expected);
+
+    let row = client
+        .query_one(
+            "SELECT 1::NUMERIC AS total, 1::MONEY AS fee, NULL::NUMERIC AS discount, 'many' AS imported",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert!(Payment::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn interval() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS lease_id, '1 day 2 hours 0.5 seconds'::INTERVAL AS duration, NULL::INTERVAL AS grace",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Lease {
+        lease_id: 1,
+        duration: std::time::Duration::from_millis(93_600_500),
+        grace: None,
+    };
+
+    assert_eq!(Lease::from_row(&row), expected);
+    assert_eq!(Lease::try_from_row(&row).unwrap(), expected);
+
+    for duration in ["'-1 hour'", "'1 month'", "'1 day -25 hours'"] {
+        let row = client
+            .query_one(
+                &format!("SELECT 1 AS lease_id, {duration}::INTERVAL AS duration, '5 minutes'::INTERVAL AS grace"),
+                &[],
+            )
+            .await
+            .unwrap();
+
+        assert!(Lease::try_from_row(&row).is_err());
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn lenient() {
+    let client = connect().await;
+
+    let row = client
+        .query_one("SELECT 1 AS draft_id, 2 AS author_user_id", &[])
+        .await
+        .unwrap();
+
+    let expected = Draft {
+        draft_id: 1,
+        title: String::new(),
+        subtitle: "untitled".to_owned(),
+        author: Person { user_id: 2 },
+        saved: false,
+    };
+
+    assert_eq!(Draft::from_row(&row), expected);
+    assert_eq!(Draft::try_from_row(&row).unwrap(), expected);
+
+    let row = client
+        .query_one("SELECT 1 AS draft_id, 'notes' AS title", &[])
+        .await
+        .unwrap();
+
+    assert!(Draft::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn column_error() {
+    let client = connect().await;
+
+    let row = client.query_one("SELECT 1 AS other", &[]).await.unwrap();
+    let error = Rating::try_from_row(&row).unwrap_err();
+    assert!(postgres_from_row::is_column_error(&error));
+
+    let row = client
+        .query_one("SELECT 'one' AS rating_id, 5 AS stars", &[])
+        .await
+        .unwrap();
+    let error = Rating::try_from_row(&row).unwrap_err();
+    assert!(!postgres_from_row::is_column_error(&error));
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn wkb() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 'tower' AS name, decode('0101000000000000000000f03f0000000000000040', 'hex') AS location",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Landmark {
+        name: "tower".to_owned(),
+        location: GeoPoint { x: 1.0, y: 2.0 },
+    };
+
+    assert_eq!(Landmark::from_row(&row), expected);
+    assert_eq!(Landmark::try_from_row(&row).unwrap(), expected);
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn boxed_str() {
+    let client = connect().await;
+
+    let row = client
+        .query_one("SELECT 'theme' AS key, NULL::TEXT AS value", &[])
+        .await
+        .unwrap();
+
+    let expected = CacheEntry {
+        key: "theme".into(),
+        value: None,
+    };
+
+    assert_eq!(CacheEntry::from_row(&row), expected);
+    assert_eq!(CacheEntry::try_from_row(&row).unwrap(), expected);
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn generic_flatten() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 3 AS revision, 'a' AS old_value, 1 AS new_first, 2 AS new_second",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Revision {
+        revision: 3,
+        old: Wrapper {
+            value: "a".to_owned(),
+        },
+        new: Pair {
+            first: 1,
+            second: 2,
+        },
+    };
+
+    assert_eq!(Revision::from_row(&row), expected);
+    assert_eq!(
+        Revision::<String, i32>::try_from_row(&row).unwrap(),
+        expected
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn optional_row() {
+    let client = connect().await;
+
+    let row = client
+        .query_opt("SELECT 1 AS rating_id, 4 AS stars WHERE false", &[])
+        .await
+        .unwrap();
+
+    assert_eq!(Rating::from_optional_row(row.as_ref()), None);
+    assert_eq!(Rating::try_from_optional_row(row.as_ref()).unwrap(), None);
+
+    let row = client
+        .query_opt("SELECT 1 AS rating_id, 4 AS stars", &[])
+        .await
+        .unwrap();
+
+    let expected = Rating {
+        rating_id: 1,
+        stars: 4,
+    };
+
+    assert_eq!(Rating::from_optional_row(row.as_ref()), Some(expected));
+    assert!(Rating::try_from_optional_row(row.as_ref())
+        .unwrap()
+        .is_some());
+}
+
+#[derive(FromRowScalar, Debug, PartialEq)]
+pub struct Kelvin(f64);
+
+#[derive(FromRowScalar, Debug, PartialEq)]
+pub struct Tagged<T> {
+    value: T,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Climate {
+    city: Tagged<String>,
+    high: Kelvin,
+    low: Option<Kelvin>,
+}
+
+#[test]
+fn scalar() {
+    use tokio_postgres::types::{FromSql, Type};
+
+    assert!(Kelvin::accepts(&Type::FLOAT8));
+    assert!(!Kelvin::accepts(&Type::TEXT));
+    assert!(Option::<Kelvin>::accepts(&Type::FLOAT8));
+    assert!(Vec::<Kelvin>::accepts(&Type::FLOAT8_ARRAY));
+    assert_eq!(
+        Kelvin::from_sql(&Type::FLOAT8, &21.5f64.to_be_bytes()).unwrap(),
+        Kelvin(21.5)
+    );
+    assert!(Kelvin::from_sql_null(&Type::FLOAT8).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn scalar_columns() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 'Oslo' AS city, 12.5::FLOAT8 AS high, NULL::FLOAT8 AS low",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Climate {
+        city: Tagged {
+            value: "Oslo".to_owned(),
+        },
+        high: Kelvin(12.5),
+        low: None,
+    };
+
+    assert_eq!(Climate::try_from_row(&row).unwrap(), expected);
+    assert_eq!(Climate::from_row(&row), expected);
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn default_precedence() {
+    let client = connect().await;
+
+    let row = client
+        .query_one("SELECT 1 AS listing_id", &[])
+        .await
+        .unwrap();
+
+    let expected = Classified {
+        listing_id: 1,
+        title: "untitled".to_owned(),
+        summary: None,
+        views: 0,
+        price: 0,
+    };
+
+    assert_eq!(Classified::from_row(&row), expected);
+    assert_eq!(Classified::try_from_row(&row).unwrap(), expected);
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS listing_id, NULL::TEXT AS title, 'cozy' AS summary, 7::INT8 AS views, 5 AS price",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Classified {
+        listing_id: 1,
+        title: String::new(),
+        summary: Some("cozy".to_owned()),
+        views: 7,
+        price: 5,
+    };
+
+    assert_eq!(Classified::from_row(&row), expected);
+    assert_eq!(Classified::try_from_row(&row).unwrap(), expected);
+}
+
+#[cfg(feature = "performance")]
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn row_mapper() {
+    let client = connect().await;
+
+    let statement = client
+        .prepare("SELECT $1::INT4 AS sensor_id, 0.5::FLOAT8 AS value")
+        .await
+        .unwrap();
+    let mapper = postgres_from_row::RowMapper::<Gauge>::new(statement.columns());
+
+    for sensor_id in 1..=2 {
+        let rows = client.query(&statement, &[&sensor_id]).await.unwrap();
+        let expected = vec![Gauge {
+            sensor_id,
+            value: 0.5,
+        }];
+
+        assert_eq!(mapper.map_rows(&rows), expected);
+        assert_eq!(mapper.try_map_rows(&rows).unwrap(), expected);
+        assert_eq!(mapper.map(&rows[0]), expected[0]);
+    }
+
+    assert_eq!(mapper.indices().get(0), Some(0));
+    assert_eq!(mapper.indices().get(1), Some(1));
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct ParentNode {
+    node_id: i32,
+    name: String,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+#[from_row(indexed, verbose, detailed)]
+pub struct TreeNode {
+    node_id: i32,
+    name: String,
+    #[from_row(flatten, prefix = "parent_", null_check = "node_id")]
+    parent: Option<Box<ParentNode>>,
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn boxed_optional_flatten() {
+    let client = connect().await;
+
+    let rows = client
+        .query(
+            "WITH RECURSIVE nodes(node_id, parent_id, name) AS (VALUES (1, NULL::INT4, 'root'), (2, 1, 'leaf')),
+            tree AS (
+                SELECT node_id, name, 1 AS depth FROM nodes WHERE parent_id IS NULL
+                UNION ALL
+                SELECT n.node_id, n.name, t.depth + 1 FROM nodes n JOIN tree t ON n.parent_id = t.node_id
+            )
+            SELECT t.node_id, t.name, p.node_id AS parent_node_id, p.name AS parent_name
+            FROM tree t LEFT JOIN nodes c ON c.node_id = t.node_id LEFT JOIN nodes p ON p.node_id = c.parent_id
+            ORDER BY t.depth",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = vec![
+        TreeNode {
+            node_id: 1,
+            name: "root".to_owned(),
+            parent: None,
+        },
+        TreeNode {
+            node_id: 2,
+            name: "leaf".to_owned(),
+            parent: Some(Box::new(ParentNode {
+                node_id: 1,
+                name: "root".to_owned(),
+            })),
+        },
+    ];
+
+    assert_eq!(TreeNode::from_rows(&rows), expected);
+    assert_eq!(TreeNode::try_from_rows(&rows).unwrap(), expected);
+    assert_eq!(TreeNode::try_from_row(&rows[1]).unwrap(), expected[1]);
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Survey {
+    #[from_row(split = ",")]
+    scores: Vec<u8>,
+    #[from_row(split = " | ")]
+    labels: Vec<String>,
+    #[from_row(split = ";")]
+    weights: Option<Vec<f64>>,
+    #[from_row(split = ",")]
+    ranks: Vec<u16>,
+    #[from_row(split = " -- end of comment -- ")]
+    comments: Vec<String>,
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn split() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT '1,2,3' AS scores, 'a | b' AS labels, NULL::TEXT AS weights, '7' AS ranks, \
+             'fine -- end of comment -- too long' AS comments",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Survey {
+        scores: vec![1, 2, 3],
+        labels: vec!["a".to_owned(), "b".to_owned()],
+        weights: None,
+        ranks: vec![7],
+        comments: vec!["fine".to_owned(), "too long".to_owned()],
+    };
+
+    assert_eq!(Survey::from_row(&row), expected);
+    assert_eq!(Survey::try_from_row(&row).unwrap(), expected);
+
+    let row = client
+        .query_one(
+            "SELECT '' AS scores, '' AS labels, '0.5' AS weights, '' AS ranks, '' AS comments",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Survey {
+        scores: Vec::new(),
+        labels: Vec::new(),
+        weights: Some(vec![0.5]),
+        ranks: Vec::new(),
+        comments: Vec::new(),
+    };
+
+    assert_eq!(Survey::try_from_row(&row).unwrap(), expected);
+
+    let row = client
+        .query_one(
+            "SELECT '1,x' AS scores, '' AS labels, '' AS weights, '' AS ranks, '' AS comments",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert!(Survey::try_from_row(&row).is_err());
+}
+
+#[cfg(feature = "json")]
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn row_to_json_types() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 'f9a1c3e2-48c5-4c8c-b3d0-7a73078e5a11'::UUID AS u, 12.50::NUMERIC AS n, \
+            -0.001::NUMERIC AS small, 123456789012345678901234567890::NUMERIC AS large, 'NaN'::NUMERIC AS nan, \
+            '10.0.0.1'::INET AS host, '10.0.0.0/8'::CIDR AS network, '::1/64'::INET AS v6, \
+            '\\xdead'::BYTEA AS bytes, NULL::UUID AS missing",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        postgres_from_row::try_row_to_json(&row).unwrap(),
+        serde_json::json!({
+            "u": "f9a1c3e2-48c5-4c8c-b3d0-7a73078e5a11",
+            "n": "12.50",
+            "small": "-0.001",
+            "large": "123456789012345678901234567890",
+            "nan": "NaN",
+            "host": "10.0.0.1",
+            "network": "10.0.0.0/8",
+            "v6": "::1/64",
+            "bytes": "\\xdead",
+            "missing": null,
+        })
+    );
+
+    #[cfg(feature = "chrono")]
+    {
+        let row = client
+            .query_one(
+                "SELECT '2024-02-29'::DATE AS d, '13:45:10.5'::TIME AS t, \
+                '2024-02-29 13:45:10'::TIMESTAMP AS ts, '2024-02-29 13:45:10.25+02'::TIMESTAMPTZ AS tz",
+                &[],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            postgres_from_row::try_row_to_json(&row).unwrap(),
+            serde_json::json!({
+                "d": "2024-02-29",
+                "t": "13:45:10.500",
+                "ts": "2024-02-29T13:45:10",
+                "tz": "2024-02-29T11:45:10.250+00:00",
+            })
+        );
+    }
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+#[from_row(rename_all = "camelCase")]
+pub struct Parcel {
+    parcel_id: i32,
+    // Reads `user_id`, since `Person` doesn't opt in to an inherited convention.
+    #[from_row(flatten, inherit_rename_all)]
+    sender: Person,
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn inherit_rename_all() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            r#"SELECT 1 AS "shipmentId", 2 AS "orderId", 3 AS customer_user_id, 4 AS "authorId", 'Ann' AS "displayName", 'ann@example.com' AS email"#,
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert!(Shipment::try_from_row(&row).is_ok());
+
+    let row = client
+        .query_one(
+            r#"SELECT 1 AS "shipmentId", 2 AS order_id, 3 AS customer_user_id, 4 AS "authorId", 'Ann' AS "displayName", 'ann@example.com' AS email"#,
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert!(Shipment::try_from_row(&row).is_err());
+
+    let row = client
+        .query_one(r#"SELECT 1 AS "parcelId", 2 AS user_id"#, &[])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        Parcel::try_from_row(&row).unwrap(),
+        Parcel {
+            parcel_id: 1,
+            sender: Person { user_id: 2 },
+        }
+    );
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+pub struct Memo {
+    memo_id: i32,
+    #[from_row(default)]
+    note: String,
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn from_row_opt() {
+    let client = connect().await;
+
+    let row = client.query_one("SELECT 1 AS memo_id", &[]).await.unwrap();
+    let expected = Memo {
+        memo_id: 1,
+        note: String::new(),
+    };
+
+    assert_eq!(Memo::from_row_opt(&row), Some(expected));
+    assert!(Memo::try_from_row_opt(&row).unwrap().is_some());
+    assert_eq!(<(i32, i32)>::from_row_opt(&row), None);
+    assert_eq!(<(i32, i32)>::try_from_row_opt(&row).unwrap(), None);
+
+    let row = client.query_one("SELECT 1 AS other", &[]).await.unwrap();
+
+    assert_eq!(Memo::from_row_opt(&row), None);
+    assert_eq!(Memo::try_from_row_opt(&row).unwrap(), None);
+
+    let row = client
+        .query_one("SELECT 'one' AS memo_id", &[])
+        .await
+        .unwrap();
+
+    assert!(Memo::try_from_row_opt(&row).is_err());
+}
+
+#[derive(FromRow, Debug)]
+pub struct Probe {
+    id: i32,
+    #[from_row(get_or_default)]
+    score: i32,
+}
+
+#[derive(FromRow, Debug)]
+pub struct BorrowedProbe<'a> {
+    id: i32,
+    name: &'a str,
+}
+
+fn opt_panic_message(convert: impl FnOnce()) -> String {
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(convert)).unwrap_err();
+    panic.downcast_ref::<String>().unwrap().clone()
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn from_row_opt_panic() {
+    let client = connect().await;
+
+    let row = client
+        .query_one("SELECT 'one' AS memo_id", &[])
+        .await
+        .unwrap();
+    let message = opt_panic_message(|| {
+        Memo::from_row_opt(&row);
+    });
+    assert!(message.starts_with("could not convert row"), "{message}");
+
+    // `from_row` would fall back to the default of `score`, `from_row_opt` must not.
+    let row = client
+        .query_one("SELECT 1 AS id, 'x'::TEXT AS score", &[])
+        .await
+        .unwrap();
+    let message = opt_panic_message(|| {
+        Probe::from_row_opt(&row);
+    });
+    assert!(message.starts_with("could not convert row"), "{message}");
+
+    let row = client
+        .query_one("SELECT 1 AS id, 2 AS name", &[])
+        .await
+        .unwrap();
+    let message = opt_panic_message(|| {
+        BorrowedProbe::from_row_opt(&row);
+    });
+    assert!(message.starts_with("could not convert row"), "{message}");
+
+    let row = client
+        .query_one("SELECT 1 AS id, 'probe' AS name", &[])
+        .await
+        .unwrap();
+    let probe = BorrowedProbe::from_row_opt(&row).unwrap();
+    assert_eq!((probe.id, probe.name), (1, "probe"));
+}
+
+fn try_label(row: &Row) -> Result<String, tokio_postgres::Error> {
+    row.try_get("label")
+}
+
+#[derive(FromRow, Debug)]
+pub struct Badge {
+    badge_id: i32,
+    #[from_row(try_from_row_fn = "try_label")]
+    label: String,
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn conversion_panic() {
+    let client = connect().await;
+
+    let row = client.query_one("SELECT 1 AS badge_id", &[]).await.unwrap();
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| Badge::from_row(&row)))
+        .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+
+    assert!(message.starts_with("conversion failed for field Badge::label"));
+
+    let row = client
+        .query_one("SELECT 1 AS badge_id, 'gold' AS label", &[])
+        .await
+        .unwrap();
+
+    assert_eq!(Badge::from_row(&row).label, "gold");
+    assert_eq!(Badge::from_row(&row).badge_id, 1);
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn rename_all() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            r#"SELECT 1 AS "authorId", 'Ann' AS "displayName", 'ann@example.com' AS email, 2 AS author_id"#,
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let author = Author::try_from_row(&row).unwrap();
+    assert_eq!(author.author_id, 1);
+    assert_eq!(author.display_name, "Ann");
+    assert_eq!(author.email_address, "ann@example.com");
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS author_id, 'Ann' AS display_name, 'ann@example.com' AS email",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert!(Author::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn tuple_struct() {
+    let client = connect().await;
+
+    let row = client
+        .query_one("SELECT 1.5::FLOAT8 AS y, 2.5::FLOAT8 AS x", &[])
+        .await
+        .unwrap();
+
+    let Point(first, second) = Point::from_row(&row);
+    assert_eq!((first, second), (1.5, 2.5));
+
+    assert!(Id::try_from_row(&row).is_err());
+
+    let row = client.query_one("SELECT 7 AS anything", &[]).await.unwrap();
+    assert_eq!(Id::try_from_row(&row).unwrap().0, 7);
+    assert!(Point::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn default() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS settings_id, 10::INT8 AS total, 'bye' AS signature",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let settings = Settings::try_from_row(&row).unwrap();
+    assert_eq!(settings.theme, None);
+    assert_eq!(settings.per_page, 0);
+    assert_eq!(settings.locale, None);
+    assert_eq!(settings.retries, 0);
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS settings_id, 10::INT8 AS total, 'bye' AS signature, 'dark' AS theme, 25::INT8 AS page_size",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let settings = Settings::from_row(&row);
+    assert_eq!(settings.theme.as_deref(), Some("dark"));
+    assert_eq!(settings.per_page, 25);
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS settings_id, 10::INT8 AS total, 'bye' AS signature, 'many' AS page_size",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert!(Settings::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn default_fn() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS settings_id, 10::INT8 AS total, 'bye' AS signature",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(Settings::from_row(&row).language, "en");
+    assert_eq!(Settings::try_from_row(&row).unwrap().language, "en");
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS settings_id, 10::INT8 AS total, 'bye' AS signature, 'nl' AS language",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(Settings::try_from_row(&row).unwrap().language, "nl");
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn skip() {
+    let client = connect().await;
+
+    let row = client
+        .query_one("SELECT 1 AS bookmark_id, 'yes' AS selected", &[])
+        .await
+        .unwrap();
+
+    let expected = Bookmark {
+        bookmark_id: 1,
+        selected: false,
+    };
+
+    assert_eq!(Bookmark::from_row(&row), expected);
+    assert_eq!(Bookmark::try_from_row(&row).unwrap(), expected);
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn try_from_fn() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 212.0::FLOAT8 AS temperature, 'hot' AS label, 'C' AS unit_name",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let measurement = Measurement::try_from_row(&row).unwrap();
+    assert_eq!(measurement.temperature, 100.0);
+    assert_eq!(measurement.label, "hot");
+    assert_eq!(measurement.unit, "C");
+
+    let row = client
+        .query_one(
+            "SELECT 212.0::FLOAT8 AS temperature, 1 AS label, 'C' AS unit",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert!(Measurement::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn from_many_rows() {
+    let client = connect().await;
+
+    let rows = client
+        .query(
+            "SELECT * FROM (VALUES (1, 'first'), (2, 'second')) AS todos (todo_id, text)",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = vec![
+        Buffered {
+            todo_id: 1,
+            text: "first".to_owned(),
+            reused: 0,
+        },
+        Buffered {
+            todo_id: 2,
+            text: "second".to_owned(),
+            reused: 0,
+        },
+    ];
+
+    assert_eq!(Buffered::from_rows(&rows), expected);
+    assert_eq!(Buffered::try_from_rows(&rows).unwrap(), expected);
+    assert_eq!(Buffered::from_rows(&[]), Vec::new());
+
+    let rows = client
+        .query(
+            "SELECT * FROM (VALUES (1, 'first'), (2, NULL)) AS todos (todo_id, text)",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert!(Buffered::try_from_rows(&rows).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn prefix() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS user_id, 2 AS assignee_user_id, 3 AS reviewer_user_id",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let assignment = Assignment::try_from_row(&row).unwrap();
+    assert_eq!(assignment.assignee.user_id, 2);
+    assert_eq!(assignment.reviewer.user_id, 3);
+    assert_eq!(User::from_row_with_prefix(&row, "reviewer_").user_id, 3);
+
+    let row = client
+        .query_one("SELECT 1 AS user_id, 2 AS assignee_user_id", &[])
+        .await
+        .unwrap();
+
+    assert!(Assignment::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn fieldless() {
+    let client = connect().await;
+
+    let row = client.query_one("SELECT 1 AS anything", &[]).await.unwrap();
+
+    let Empty {} = Empty::from_row(&row);
+    let Empty {} = Empty::try_from_row(&row).unwrap();
+    let Unit = Unit::try_from_row(&row).unwrap();
+
+    assert!(Empty::COLUMNS.is_empty());
+    assert!(Unit::COLUMNS.is_empty());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn from_row_fn() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS location_id, 1.5::FLOAT8 AS lat, 2.5::FLOAT8 AS lon",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let location = Location::from_row(&row);
+    assert_eq!(location.coordinates, (1.5, 2.5));
+    assert_eq!(location.fallback, (1.5, 2.5));
+
+    let location = Location::try_from_row(&row).unwrap();
+    assert_eq!(location.location_id, 1);
+    assert_eq!(location.fallback, (1.5, 2.5));
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn case_insensitive() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            r#"SELECT 1 AS "STREET_ID", 'Main' AS "Name", 3 AS "GRÖßE""#,
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let street = Street::try_from_row(&row).unwrap();
+    assert_eq!(street.street_id, 1);
+    assert_eq!(street.name, "Main");
+    assert_eq!(street.size, 3);
+    assert_eq!(Street::from_row(&row).size, 3);
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn indexed() {
+    let client = connect().await;
+
+    let waypoint = |waypoint_id, label: &str, altitude, user_id| Waypoint {
+        waypoint_id,
+        label: label.to_owned(),
+        altitude,
+        author: ShuffledAuthor {
+            user_id,
+            username: "remo".to_owned(),
+        },
+    };
+
+    let rows = client
+        .query(
+            "SELECT 'remo' AS author_username, name, author_user_id, waypoint_id \
+             FROM (VALUES ('start', 1, 3), ('end', 2, 4)) AS waypoints (name, waypoint_id, author_user_id)",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = vec![waypoint(1, "start", 0, 3), waypoint(2, "end", 0, 4)];
+
+    assert_eq!(Waypoint::from_rows(&rows), expected);
+    assert_eq!(Waypoint::try_from_rows(&rows).unwrap(), expected);
+
+    let indices = Waypoint::resolve_indices(rows[0].columns());
+    assert_eq!(Waypoint::from_row_indexed(&rows[1], &indices), expected[1]);
+    assert_eq!(
+        Waypoint::try_from_row_indexed(&rows[1], &indices).unwrap(),
+        expected[1]
+    );
+
+    let rows = client
+        .query(
+            "SELECT 5 AS altitude, 'top' AS label, 1 AS waypoint_id, 3 AS author_user_id, 'remo' AS author_username",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(Waypoint::from_rows(&rows), vec![waypoint(1, "top", 5, 3)]);
+
+    let rows = client
+        .query(
+            "SELECT 'top' AS label, waypoint_id, 3 AS author_user_id, 'remo' AS author_username \
+             FROM (VALUES (1), (NULL)) AS waypoints (waypoint_id)",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert!(Waypoint::try_from_rows(&rows).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn optional() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS settings_id, 10::INT8 AS total, 'bye' AS signature",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(Settings::try_from_row(&row).unwrap().timezone, None);
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS settings_id, 10::INT8 AS total, 'bye' AS signature, 'UTC' AS timezone",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(Settings::from_row(&row).timezone.as_deref(), Some("UTC"));
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS settings_id, 10::INT8 AS total, 'bye' AS signature, 1 AS timezone",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert!(Settings::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn with() {
+    let client = connect().await;
+
+    let row = client
+        .query_one("SELECT '  Title ' AS title", &[])
+        .await
+        .unwrap();
+
+    let article = Article::try_from_row(&row).unwrap();
+    assert_eq!(article.title, "Title");
+    assert_eq!(article.subtitle, "");
+
+    let row = client
+        .query_one("SELECT ' Title' AS title, 'Sub  ' AS subtitle", &[])
+        .await
+        .unwrap();
+
+    assert_eq!(Article::from_row(&row).subtitle, "Sub");
+
+    let row = client.query_one("SELECT 1 AS title", &[]).await.unwrap();
+
+    assert!(Article::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn column_index() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT count(*), avg(value)::FLOAT8 FROM (VALUES (1), (2)) AS samples (value)",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let statistics = Statistics::try_from_row(&row).unwrap();
+    assert_eq!(statistics.total, 2);
+    assert_eq!(statistics.average, 1.5);
+
+    let row = client
+        .query_one("SELECT count(*) FROM (VALUES (1)) AS samples (value)", &[])
+        .await
+        .unwrap();
+
+    assert!(Statistics::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn tagged_enum() {
+    let client = connect().await;
+
+    let row = client
+        .query_one("SELECT 'user' AS kind, 1 AS user_id, 'remo' AS name", &[])
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        Account::try_from_row(&row).unwrap(),
+        Account::User { user_id: 1, username } if username == "remo"
+    ));
+
+    let row = client
+        .query_one("SELECT 'Guest' AS kind", &[])
+        .await
+        .unwrap();
+
+    assert!(matches!(Account::from_row(&row), Account::Guest));
+
+    let row = client
+        .query_one("SELECT 'admin' AS kind", &[])
+        .await
+        .unwrap();
+
+    assert!(Account::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn verbose() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS visit_id, '/' AS page, 2 AS visitor_bookmark_id",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let expected = Visit {
+        visit_id: 1,
+        page: "/".to_owned(),
+        visitor: Bookmark {
+            bookmark_id: 2,
+            selected: false,
+        },
+    };
+
+    assert_eq!(Visit::try_from_row_verbose(&row).unwrap(), expected);
+
+    let row = client
+        .query_one(
+            "SELECT 'one' AS visit_id, 2 AS page, 3 AS visitor_bookmark_id",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let errors = Visit::try_from_row_verbose(&row).unwrap_err();
+    let columns: Vec<&str> = errors.iter().map(|(column, _)| column.as_str()).collect();
+    assert_eq!(columns, ["visit_id", "page"]);
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn missing_column_panic() {
+    let client = connect().await;
+
+    let row = client
+        .query_one("SELECT 1 AS visit_id, 2 AS visitor_bookmark_id", &[])
+        .await
+        .unwrap();
+
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| Visit::from_row(&row)))
+        .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+
+    assert!(
+        message.starts_with(r#"failed to get column "page" for field Visit::page"#),
+        "{message}"
+    );
+
+    let row = client
+        .query_one("SELECT 1 AS visit_id, '/' AS page", &[])
+        .await
+        .unwrap();
+
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| Visit::from_row(&row)))
+        .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+
+    assert!(
+        message.starts_with(
+            r#"failed to get column "visitor_bookmark_id" for field Bookmark::bookmark_id"#
+        ),
+        "{message}"
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn wrap() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 'Ann' AS name, '\\x0102'::BYTEA AS avatar, 'Hi' AS bio, 1 AS user_id, 2 AS editor_user_id",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let profile = Profile::try_from_row(&row).unwrap();
+    assert_eq!(&*profile.name, "Ann");
+    assert_eq!(&*profile.avatar, [1, 2]);
+    assert_eq!(profile.bio.as_str(), "Hi");
+    assert_eq!(profile.owner.user_id, 1);
+    assert_eq!(profile.editor.user_id, 2);
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn build_with() {
+    let client = connect().await;
+
+    let row = client
+        .query_one("SELECT 1 AS start, 2 AS \"end\"", &[])
+        .await
+        .unwrap();
+
+    let range = Range::try_from_row(&row).unwrap();
+    assert_eq!((range.start, range.end), (1, 2));
+
+    let row = client
+        .query_one("SELECT 3 AS start, 2 AS \"end\"", &[])
+        .await
+        .unwrap();
+
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        Range::from_row(&row);
+    }))
+    .unwrap_err();
+    assert_eq!(panic.downcast_ref::<&str>(), Some(&"invalid range"));
+
+    let row = client
+        .query_one("SELECT 5 AS lower, 2 AS upper", &[])
+        .await
+        .unwrap();
+
+    let interval = Interval::try_from_row(&row).unwrap();
+    assert_eq!((interval.lower, interval.upper), (2, 5));
+
+    let row = client
+        .query_one("SELECT 250::INT8 AS cents, 'damaged' AS reason", &[])
+        .await
+        .unwrap();
+
+    let refund = billing::Refund::from_row(&row);
+    assert_eq!(refund.cents(), 250);
+    assert_eq!(refund.reason(), Some("damaged"));
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn nested_prefix() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS delivery_id, 2 AS order_order_id, 3 AS order_customer_user_id, 4 AS customer_user_id",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let delivery = Delivery::try_from_row(&row).unwrap();
+    assert_eq!(delivery.delivery_id, 1);
+    assert_eq!(delivery.order.order_id, 2);
+    assert_eq!(delivery.order.customer.user_id, 3);
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS delivery_id, 2 AS order_order_id, 4 AS customer_user_id",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert!(Delivery::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn tuples() {
+    let client = connect().await;
+
+    let row = client
+        .query_one("SELECT 1, 'one', NULL::FLOAT8", &[])
+        .await
+        .unwrap();
+
+    let (id,): (i32,) = FromRow::from_row(&row);
+    assert_eq!(id, 1);
+
+    let tuple: (i32, String, Option<f64>) = FromRow::try_from_row(&row).unwrap();
+    assert_eq!(tuple, (1, "one".to_owned(), None));
+
+    assert!(<(i32, i32)>::try_from_row(&row).is_err());
+    assert!(<(i32, String, Option<f64>, i32)>::try_from_row(&row).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn null_check() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS ticket_id, 2 AS user_id, NULL::INT4 AS reviewer_user_id",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let ticket = Ticket::try_from_row(&row).unwrap();
+    assert_eq!(ticket.assignee.map(|user| user.user_id), Some(2));
+    assert!(ticket.reviewer.is_none());
+
+    let ticket = Ticket::from_row(&row);
+    assert_eq!(ticket.ticket_id, 1);
+    assert!(ticket.reviewer.is_none());
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS ticket_id, NULL::INT4 AS user_id, 3 AS reviewer_user_id",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let ticket = Ticket::try_from_row(&row).unwrap();
+    assert!(ticket.assignee.is_none());
+    assert_eq!(ticket.reviewer.map(|user| user.user_id), Some(3));
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn params_round_trip() {
+    let client = connect().await;
+
+    let purchase = Purchase {
+        purchase_id: 1,
+        note: "gift".to_owned(),
+        buyer: Buyer { user_id: 2 },
+    };
+
+    assert_eq!(purchase.params().len(), Purchase::PARAM_COLUMNS.len());
+
+    let row = client
+        .query_one(
+            "SELECT $1::INT4 AS purchase_id, $2::TEXT AS note, $3::INT4 AS buyer_user_id",
+            &purchase.params(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(Purchase::from_row(&row), purchase);
+}
+
+#[tokio::test]
+#[ignore = "needs a database, see `connect`"]
+async fn detailed_errors() {
+    let client = connect().await;
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS receipt_id, 250::INT8 AS amount_cents, 2 AS payer_user_id",
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        Receipt::try_from_row_detailed(&row).unwrap(),
+        Receipt {
+            receipt_id: 1,
+            amount: 250,
+            payer: Payer { user_id: 2 },
+        }
+    );
+
+    let row = client
+        .query_one(
+            "SELECT 1 AS receipt_id, 'free'::TEXT AS amount_cents, 2 AS payer_user_id",
+            &[],
+        )
+        .await
+        .unwrap();
+    let error = Receipt::try_from_row_detailed(&row).unwrap_err();
+    assert_eq!(error.column(), "amount_cents");
+    assert_eq!(error.field(), "amount");
+
+    let row = client
+        .query_one("SELECT 1 AS receipt_id, 250::INT8 AS amount_cents", &[])
+        .await
+        .unwrap();
+    let error = Receipt::try_from_row_detailed(&row).unwrap_err();
+    assert_eq!(error.column(), "payer_user_id");
+    assert_eq!(error.field(), "user_id");
 }